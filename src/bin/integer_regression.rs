@@ -1,13 +1,15 @@
 use polars::prelude::*;
 use rush::gp::args::PushArgs;
 use rush::gp::args::SearchDirection;
+use rush::gp::checkpoint::CheckpointError;
 use rush::gp::gp_loop;
 use rush::gp::selection::Selection;
 use rush::gp::utils::polars_to_gene;
+use rush::instruction;
 use rush::instructions::code::exec_if;
 use rush::instructions::common::*;
 use rush::instructions::numeric::*;
-use rush::push::interpreter::interpret_program;
+use rush::push::interpreter::{InterpreterError, interpret_program};
 use rush::push::state::{EMPTY_STATE, Gene};
 use rust_decimal::prelude::FromPrimitive;
 use rust_decimal::{Decimal, dec};
@@ -28,21 +30,25 @@ fn train_data() -> DataFrame {
 fn instructions() -> Vec<Gene> {
     vec![
         Gene::Place(0),
-        Gene::StateFunc(int_add),
-        Gene::StateFunc(int_sub),
-        Gene::StateFunc(int_mult),
-        Gene::StateFunc(int_div),
-        Gene::StateFunc(int_dup),
-        Gene::StateFunc(int_equal),
-        Gene::StateFunc(exec_dup),
-        // Gene::StateFunc(exec_if),
+        Gene::StateFunc(instruction!(int_add)),
+        Gene::StateFunc(instruction!(int_sub)),
+        Gene::StateFunc(instruction!(int_mult)),
+        Gene::StateFunc(instruction!(int_div)),
+        Gene::StateFunc(instruction!(int_dup)),
+        Gene::StateFunc(instruction!(int_equal)),
+        Gene::StateFunc(instruction!(exec_dup)),
+        // Gene::StateFunc(instruction!(exec_if)),
         Gene::Close,
         Gene::GeneInt(1),
         Gene::GeneInt(0),
     ]
 }
 
-fn error_function(push_args: &PushArgs, data: &DataFrame, push_program: Vec<Gene>) -> Vec<Decimal> {
+fn error_function(
+    push_args: &PushArgs,
+    data: &DataFrame,
+    push_program: Vec<Gene>,
+) -> Result<Vec<Decimal>, InterpreterError> {
     let mut error_vec: Vec<Decimal> = vec![];
 
     let y = data
@@ -65,7 +71,14 @@ fn error_function(push_args: &PushArgs, data: &DataFrame, push_program: Vec<Gene
         }
         state.exec.extend(push_program.clone().into_iter()); // load the program
         state.input.extend(inputs.clone().into_iter()); // Make inputs available to the state
-        interpret_program(&mut state, push_args.step_limit, push_args.max_stack_size);
+        interpret_program(
+            &mut state,
+            push_args.step_limit,
+            push_args.max_stack_size,
+            push_args.max_output_length,
+            push_args.max_points,
+            push_args.overflow,
+        )?;
         if let Some(top_int) = state.int.pop() {
             error_vec.push(Decimal::from_i128((y[n] - top_int).abs()).unwrap());
         } else {
@@ -73,10 +86,10 @@ fn error_function(push_args: &PushArgs, data: &DataFrame, push_program: Vec<Gene
         }
     }
 
-    error_vec
+    Ok(error_vec)
 }
 
-fn main() {
+fn main() -> Result<(), CheckpointError> {
     let mut push_args = PushArgs::new();
     push_args.training_data = Some(train_data());
     push_args.instructions = Some(instructions());
@@ -87,5 +100,6 @@ fn main() {
     push_args.elitism = true;
     // push_args.search_direction = SearchDirection::Max;
 
-    gp_loop(push_args);
+    gp_loop(push_args)?;
+    Ok(())
 }
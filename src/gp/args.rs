@@ -1,10 +1,17 @@
 use crate::gp::selection::Selection;
 use crate::gp::variation::Variation;
+use crate::instructions::utils::Overflow;
+use crate::push::erc::Erc;
+use crate::push::interpreter::InterpreterError;
 use crate::push::state::Gene;
 use polars::prelude::*;
 use rust_decimal::prelude::*;
 use std::collections::HashMap;
 
+/// Evaluates a push program on every row of the data, returning one error per row.
+pub type ErrorFunction =
+    fn(&PushArgs, &DataFrame, Vec<Gene>) -> Result<Vec<Decimal>, InterpreterError>;
+
 #[derive(Clone, Copy)]
 pub enum ClosingType {
     Specified,
@@ -12,6 +19,12 @@ pub enum ClosingType {
     None,
 }
 
+#[derive(Clone, Copy)]
+pub enum DownsampleType {
+    Random,   // Pick the downsample uniformly at random
+    Informed, // Pick the cases that are furthest apart based on parent performance
+}
+
 #[derive(Clone, Copy)]
 pub enum SearchDirection {
     Min,
@@ -22,26 +35,41 @@ pub enum SearchDirection {
 pub struct PushArgs {
     pub alignment_deviation: Decimal, // For alternation, std dev of deviation of index when alternating
     pub alternation_rate: usize, // For alternation, prob of switching parents at each location. A number 0-100
-    pub closes: ClosingType,     // How push should automatically place Gene::Close into a plushy
-    pub dont_end: bool,          // If true, keep running until limit regardless of success
-    // pub downsample: bool, // Whether or not to downsample. TODO later with all the related args
-    pub elitism: bool, // Whether to always add the best individual to next generation
-    pub error_function: Option<fn(&PushArgs, &DataFrame, Vec<Gene>) -> Vec<Decimal>>, // The error function
+    pub checkpoint_gens: usize,  // Generations between checkpoints
+    pub checkpoint_path: Option<String>, // Where to write checkpoints. If None, no checkpoints are written
+    pub closes: ClosingType, // How push should automatically place Gene::Close into a plushy
+    pub constant_mutation_rate: f64, // For constant mutation, prob each numeric literal is perturbed
+    pub constant_mutation_stdev: Decimal, // For constant mutation, std dev of the gaussian noise added to literals
+    pub dont_end: bool,                   // If true, keep running until limit regardless of success
+    pub downsample: bool, // Whether to evaluate each generation on a subset of the training cases
+    pub downsample_rate: f64, // Proportion of the training cases used in each downsample
+    pub downsample_type: DownsampleType, // How the cases in a downsample are picked
+    pub ds_parent_gens: usize, // For informed downsampling, generations between case distance updates
+    pub ds_parent_rate: f64, // For informed downsampling, proportion of parents evaluated on all cases to measure case distances
+    pub elitism: bool,       // Whether to always add the best individual to next generation
+    pub ercs: Vec<Erc>,      // Ephemeral random constants sampled along with the instructions
+    pub error_function: Option<ErrorFunction>, // The error function, returning one error per row of the data
+    pub error_penalty: Decimal, // Size of the error given to every case of a program that can't be evaluated, negated when maximizing
     pub instructions: Option<Vec<Gene>>, // Instructions to use in a run
-    pub max_generations: usize,          // Max amount of generations
-    pub max_init_plushy_size: usize,     // max initial plushy size
-    pub max_stack_size: usize,           // max size a stack is allowed to reach during execution
-    pub parent_selection: Selection,     // Selection to use, TODO change this later.
-    pub pop_size: usize,                 // Population size
-    pub replacement_rate: f64,           // For uniform replacement, rate items replaced
-    pub use_simplification: bool,        // Whether to use simplification at end of run
+    pub max_generations: usize, // Max amount of generations
+    pub max_init_plushy_size: usize, // max initial plushy size
+    pub max_output_length: usize, // max chars print instructions can write during execution
+    pub max_points: usize,      // max points in a piece of code instructions can create
+    pub max_stack_size: usize,  // max size a stack is allowed to reach during execution
+    pub overflow: Overflow,     // What int arithmetic does when a result doesn't fit in an i128
+    pub parent_selection: Selection, // Selection to use, TODO change this later.
+    pub pop_size: usize,        // Population size
+    pub replacement_rate: f64,  // For uniform replacement, rate items replaced
+    pub use_simplification: bool, // Whether to use simplification at end of run
     pub search_direction: SearchDirection, // Whether the problem is a minimization or maximization problem
+    pub seed: Option<u64>, // Seed for the run's rng. If None, a random seed is chosen and printed
     pub simplification_k: usize, // Max amt of genes to attempt removal during one round of simplification process
     pub simplification_steps: usize, // How many attempts to find simplified genomes
     pub simplification_verbose: bool, // Whether to send extra messages about simplification or not
-    pub solution_error_threshold: Decimal, // Max total error for solutions
-    pub use_single_thread: bool, // if true, only single threaded
-    pub step_limit: usize,       // Amount of steps a push interpreter can run for
+    pub solution_error_threshold: Decimal, // Max total error for solutions, min total fitness when maximizing
+    pub use_single_thread: bool,           // if true, only single threaded
+    pub step_limit: usize,                 // Amount of steps a push interpreter can run for
+    pub test_new_best: bool, // Whether to evaluate each new best individual on the testing data during the run
     pub testing_data: Option<DataFrame>, // The testing data, must be formatted the same as training data
     pub tournament_size: usize,          // Tournament size for tournament selection
     pub training_data: Option<DataFrame>, // The training data, must be formatted the same as testing data
@@ -58,25 +86,41 @@ impl PushArgs {
         Self {
             alignment_deviation: dec!(2.0),
             alternation_rate: 10,
+            checkpoint_gens: 10,
+            checkpoint_path: None,
             closes: ClosingType::Specified,
+            constant_mutation_rate: 0.5,
+            constant_mutation_stdev: dec!(1.0),
             dont_end: false,
+            downsample: false,
+            downsample_rate: 0.05,
+            downsample_type: DownsampleType::Random,
+            ds_parent_gens: 10,
+            ds_parent_rate: 0.01,
             elitism: false,
+            ercs: vec![],
             error_function: None,
+            error_penalty: dec!(1000000.0),
             instructions: None,
             max_generations: 1000,
             max_init_plushy_size: 100,
+            max_output_length: 1000,
+            max_points: 100,
             max_stack_size: 100,
+            overflow: Overflow::Checked,
             parent_selection: Selection::Lexicase,
             pop_size: 1000,
             replacement_rate: 0.1,
             use_simplification: true,
             search_direction: SearchDirection::Min,
+            seed: None,
             simplification_k: 4,
             simplification_steps: 1000,
             simplification_verbose: true,
             use_single_thread: false,
             solution_error_threshold: dec!(0.0),
             step_limit: 1000,
+            test_new_best: false,
             testing_data: None,
             tournament_size: 5,
             training_data: None,
@@ -84,4 +128,15 @@ impl PushArgs {
             variation: map,
         }
     }
+
+    /// The genes plushies are built from, the instructions followed by an
+    /// `Erc` gene for each ERC.
+    pub fn gene_pool(&self) -> Vec<Gene> {
+        let mut genes = self
+            .instructions
+            .clone()
+            .expect("Must provide instructions");
+        genes.extend(self.ercs.iter().cloned().map(Gene::Erc));
+        genes
+    }
 }
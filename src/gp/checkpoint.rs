@@ -0,0 +1,248 @@
+//! # Checkpoints
+//!
+//! A checkpoint holds everything needed to continue a run from the end of a
+//! generation: the seed, the generation number, the population with its
+//! errors, the best individual seen so far, and the case distances for
+//! informed down-sampling. The rng of every generation is derived from the
+//! seed and the generation number, so a resumed run picks up exactly where
+//! the original left off.
+//!
+//! Checkpoints are written with the binary format of `push::serialization`,
+//! which stores instructions by name so a checkpoint stays valid across
+//! rebuilds.
+
+use crate::gp::args::PushArgs;
+use crate::gp::individual::Individual;
+use crate::push::serialization::{from_binary, to_binary};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const HEADER: &[u8] = b"rush_checkpoint 2\n";
+
+/// Why a checkpoint couldn't be saved, loaded or resumed from.
+#[derive(Debug)]
+pub enum CheckpointError {
+    /// Reading or writing the checkpoint file failed.
+    Io(io::Error),
+    /// The file isn't a checkpoint or its contents can't be decoded.
+    Format(String),
+    /// The checkpoint has a different population size than the arguments.
+    PopSize { checkpoint: usize, args: usize },
+    /// The checkpoint has a different seed than the arguments.
+    Seed { checkpoint: u64, args: u64 },
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckpointError::Io(err) => write!(f, "Checkpoint io failed: {}", err),
+            CheckpointError::Format(msg) => write!(f, "Invalid checkpoint: {}", msg),
+            CheckpointError::PopSize { checkpoint, args } => write!(
+                f,
+                "Checkpoint has a population size of {} but pop_size is {}",
+                checkpoint, args
+            ),
+            CheckpointError::Seed { checkpoint, args } => write!(
+                f,
+                "Checkpoint has seed {} but the arguments have seed {}",
+                checkpoint, args
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+impl From<io::Error> for CheckpointError {
+    fn from(err: io::Error) -> Self {
+        CheckpointError::Io(err)
+    }
+}
+
+impl From<bincode::Error> for CheckpointError {
+    fn from(err: bincode::Error) -> Self {
+        CheckpointError::Format(err.to_string())
+    }
+}
+
+/// The state of a run at the end of a generation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub seed: u64,                       // The seed the run was started with
+    pub generation: usize,               // The generation the population belongs to
+    pub pop_size: usize,                 // Population size of the run
+    pub best_individual: Individual, // Best individual seen so far, evaluated on all training cases
+    pub case_distances: Vec<Vec<usize>>, // Case distances for informed downsampling, empty otherwise
+    pub population: Vec<Individual>,     // The population sorted best first
+}
+
+impl Checkpoint {
+    /// Checks that the arguments a run is resumed with agree with the
+    /// checkpoint.
+    pub fn check_args(&self, push_args: &PushArgs) -> Result<(), CheckpointError> {
+        if push_args.pop_size != self.pop_size {
+            return Err(CheckpointError::PopSize {
+                checkpoint: self.pop_size,
+                args: push_args.pop_size,
+            });
+        }
+        if let Some(seed) = push_args.seed
+            && seed != self.seed
+        {
+            return Err(CheckpointError::Seed {
+                checkpoint: self.seed,
+                args: seed,
+            });
+        }
+        Ok(())
+    }
+
+    /// Writes the checkpoint to `path`. The checkpoint is written to a
+    /// temporary file first and then renamed, so a run dying mid write never
+    /// leaves a broken checkpoint behind.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        let path = path.as_ref();
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        fs::write(&tmp_path, self.to_bytes()?)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Reads a checkpoint written by `save`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CheckpointError> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, CheckpointError> {
+        let mut bytes = HEADER.to_vec();
+        bytes.extend(to_binary(self)?);
+        Ok(bytes)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, CheckpointError> {
+        match bytes.strip_prefix(HEADER) {
+            Some(body) => Ok(from_binary(body)?),
+            None => Err(CheckpointError::Format("Not a rush checkpoint".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gp::genome::plushy_to_push;
+    use crate::instructions::numeric::*;
+    use crate::push::serialization::all_gene_kinds;
+    use crate::push::state::Gene;
+    use rust_decimal::{Decimal, dec};
+
+    #[test]
+    fn checkpoint_round_trip_test() {
+        let ind = |plushy: Vec<Gene>, errors: Vec<Decimal>| Individual {
+            push_program: Some(plushy_to_push(plushy.clone())),
+            plushy,
+            total_fitness: Some(errors.iter().sum()),
+            fitness_cases: Some(errors),
+        };
+        let checkpoint = Checkpoint {
+            seed: 42,
+            generation: 7,
+            pop_size: 2,
+            best_individual: ind(vec![Gene::GeneInt(1)], vec![dec!(0.5), dec!(1.0)]),
+            case_distances: vec![vec![0, 3], vec![3, 0]],
+            population: vec![
+                ind(all_gene_kinds(), vec![dec!(0.5), dec!(1.0)]),
+                ind(vec![], vec![dec!(2.0), dec!(3.0)]),
+            ],
+        };
+        assert_eq!(
+            checkpoint,
+            Checkpoint::from_bytes(&checkpoint.to_bytes().unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_checkpoint_test() {
+        assert_eq!(
+            "Invalid checkpoint: Not a rush checkpoint",
+            Checkpoint::from_bytes(b"not a checkpoint")
+                .unwrap_err()
+                .to_string()
+        );
+
+        let ind = Individual {
+            plushy: vec![Gene::StateFunc(instruction!(int_add))],
+            push_program: Some(vec![Gene::StateFunc(instruction!(int_add))]),
+            total_fitness: Some(dec!(0.0)),
+            fitness_cases: Some(vec![dec!(0.0)]),
+        };
+        let checkpoint = Checkpoint {
+            seed: 42,
+            generation: 7,
+            pop_size: 1,
+            best_individual: ind.clone(),
+            case_distances: vec![],
+            population: vec![ind],
+        };
+        let bytes = checkpoint.to_bytes().unwrap();
+        // Swap the instruction name for an unregistered one of the same length.
+        let start = bytes
+            .windows(7)
+            .position(|window| window == b"int_add")
+            .unwrap();
+        let mut renamed = bytes.clone();
+        renamed[start..start + 7].copy_from_slice(b"int_xyz");
+        let err = Checkpoint::from_bytes(&renamed).unwrap_err();
+        assert_eq!(
+            "Invalid checkpoint: Unknown instruction: int_xyz",
+            err.to_string()
+        );
+
+        let truncated = &bytes[..bytes.len() - 1];
+        assert!(matches!(
+            Checkpoint::from_bytes(truncated),
+            Err(CheckpointError::Format(_))
+        ));
+    }
+
+    #[test]
+    fn check_args_test() {
+        let checkpoint = Checkpoint {
+            seed: 42,
+            generation: 7,
+            pop_size: 10,
+            best_individual: Individual {
+                plushy: vec![],
+                push_program: Some(vec![]),
+                total_fitness: Some(dec!(0.0)),
+                fitness_cases: Some(vec![dec!(0.0)]),
+            },
+            case_distances: vec![],
+            population: vec![],
+        };
+        let mut push_args = PushArgs::new();
+        push_args.pop_size = 10;
+        push_args.seed = None;
+        assert!(checkpoint.check_args(&push_args).is_ok());
+        push_args.seed = Some(42);
+        assert!(checkpoint.check_args(&push_args).is_ok());
+
+        push_args.seed = Some(41);
+        assert_eq!(
+            "Checkpoint has seed 42 but the arguments have seed 41",
+            checkpoint.check_args(&push_args).unwrap_err().to_string()
+        );
+        push_args.pop_size = 11;
+        assert!(matches!(
+            checkpoint.check_args(&push_args),
+            Err(CheckpointError::PopSize {
+                checkpoint: 10,
+                args: 11
+            })
+        ));
+    }
+}
@@ -0,0 +1,189 @@
+//! # Down-sampled Lexicase
+//!
+//! Instead of evaluating every individual on every training case, each
+//! generation is evaluated on a subset (a downsample) of the training cases.
+//! The downsample can be picked uniformly at random or, with informed
+//! down-sampling, by picking the cases that are furthest from each other
+//! based on how a sample of the parents performed on them.
+//!
+//! Boldi et al. on informed down-sampling: https://arxiv.org/abs/2301.01488
+
+use crate::gp::args::SearchDirection;
+use polars::prelude::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use rust_decimal::Decimal;
+
+/// The number of cases in a downsample. Always keeps at least one case.
+fn downsample_size(case_count: usize, downsample_rate: f64) -> usize {
+    ((case_count as f64 * downsample_rate).round() as usize).clamp(1, case_count.max(1))
+}
+
+/// Picks a uniformly random downsample of the training cases.
+/// Returned indices are sorted.
+pub fn select_downsample_random(
+    case_count: usize,
+    downsample_rate: f64,
+    rng: &mut impl Rng,
+) -> Vec<usize> {
+    let mut cases: Vec<usize> = (0..case_count).collect();
+    cases.shuffle(rng);
+    cases.truncate(downsample_size(case_count, downsample_rate));
+    cases.sort();
+    cases
+}
+
+/// Picks a downsample with farthest first traversal over the case distances.
+/// Starts from a random case, then repeatedly adds the case whose distance to
+/// its closest already chosen case is largest. Ties are broken randomly.
+/// Returned indices are sorted.
+pub fn select_downsample_maxmin(
+    distances: &[Vec<usize>],
+    downsample_rate: f64,
+    rng: &mut impl Rng,
+) -> Vec<usize> {
+    let mut remaining: Vec<usize> = (0..distances.len()).collect();
+    remaining.shuffle(rng);
+    let size = downsample_size(distances.len(), downsample_rate);
+
+    let mut chosen: Vec<usize> = Vec::with_capacity(size);
+    if let Some(first) = remaining.pop() {
+        chosen.push(first);
+    }
+    while chosen.len() < size {
+        let mut best_pos = 0;
+        let mut best_dist = 0;
+        for (pos, case) in remaining.iter().enumerate() {
+            let min_dist = chosen
+                .iter()
+                .map(|other| distances[*case][*other])
+                .min()
+                .unwrap();
+            if pos == 0 || min_dist > best_dist {
+                best_pos = pos;
+                best_dist = min_dist;
+            }
+        }
+        chosen.push(remaining.remove(best_pos));
+    }
+    chosen.sort();
+    chosen
+}
+
+/// Creates the starting case distances for informed down-sampling. Every
+/// pair of distinct cases is equally far apart, so the first downsample is
+/// effectively random.
+pub fn initial_case_distances(case_count: usize) -> Vec<Vec<usize>> {
+    (0..case_count)
+        .map(|i| {
+            (0..case_count)
+                .map(|j| if i == j { 0 } else { 1 })
+                .collect()
+        })
+        .collect()
+}
+
+/// Computes the distance between each pair of training cases from the errors
+/// of a sample of parents on all of the cases. A parent solves a case if it has
+/// the best error on that case among the sampled parents. The distance between
+/// two cases is the number of parents that solve exactly one of them.
+pub fn case_distances(errors: &[Vec<Decimal>], direction: SearchDirection) -> Vec<Vec<usize>> {
+    let case_count = errors.first().map_or(0, |errs| errs.len());
+    let best: Vec<Decimal> = (0..case_count)
+        .map(|case| {
+            let case_errors = errors.iter().map(|errs| errs[case]);
+            match direction {
+                SearchDirection::Min => case_errors.min().unwrap(),
+                SearchDirection::Max => case_errors.max().unwrap(),
+            }
+        })
+        .collect();
+    let solved: Vec<Vec<bool>> = errors
+        .iter()
+        .map(|errs| errs.iter().zip(best.iter()).map(|(e, b)| e == b).collect())
+        .collect();
+
+    (0..case_count)
+        .map(|i| {
+            (0..case_count)
+                .map(|j| solved.iter().filter(|s| s[i] != s[j]).count())
+                .collect()
+        })
+        .collect()
+}
+
+/// Creates a new DataFrame containing only the rows at the given indices.
+pub fn downsample_data(data: &DataFrame, indices: &[usize]) -> DataFrame {
+    let idx = IdxCa::from_vec(
+        "downsample".into(),
+        indices.iter().map(|ndx| *ndx as IdxSize).collect(),
+    );
+    data.take(&idx).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use rust_decimal::dec;
+
+    #[test]
+    fn select_downsample_random_test() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let cases = select_downsample_random(20, 0.25, &mut rng);
+        assert_eq!(5, cases.len());
+        assert!(cases.windows(2).all(|w| w[0] < w[1]));
+        assert!(cases.iter().all(|case| *case < 20));
+
+        // Always at least one case
+        let cases = select_downsample_random(20, 0.0, &mut rng);
+        assert_eq!(1, cases.len());
+    }
+
+    #[test]
+    fn select_downsample_maxmin_test() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        // Cases 0, 1, and 2 are identical to each other and far from case 3.
+        let distances = vec![
+            vec![0, 0, 0, 5],
+            vec![0, 0, 0, 5],
+            vec![0, 0, 0, 5],
+            vec![5, 5, 5, 0],
+        ];
+        for _ in 0..10 {
+            let cases = select_downsample_maxmin(&distances, 0.5, &mut rng);
+            assert_eq!(2, cases.len());
+            assert!(cases.contains(&3));
+        }
+    }
+
+    #[test]
+    fn case_distances_test() {
+        let errors = vec![
+            vec![dec!(0.0), dec!(0.0), dec!(3.0)],
+            vec![dec!(0.0), dec!(2.0), dec!(1.0)],
+            vec![dec!(1.0), dec!(2.0), dec!(1.0)],
+        ];
+        // Solved: [t, t, f], [t, f, t], [f, f, t]
+        let distances = case_distances(&errors, SearchDirection::Min);
+        assert_eq!(vec![vec![0, 1, 2], vec![1, 0, 3], vec![2, 3, 0]], distances);
+    }
+
+    #[test]
+    fn downsample_data_test() {
+        let data = df!(
+            "x0" => [1, 2, 3, 4],
+            "y" => [10, 20, 30, 40],
+        )
+        .unwrap();
+        let expected = df!(
+            "x0" => [2, 4],
+            "y" => [20, 40],
+        )
+        .unwrap();
+        assert!(expected.equals(&downsample_data(&data, &[1, 3])));
+    }
+}
@@ -1,20 +1,13 @@
-use crate::instructions::code::{
-    exec_do_count, exec_do_range, exec_do_times, exec_do_while, exec_if, exec_when, exec_while,
-};
-use crate::instructions::common::{
-    exec_dup, exec_dup_times, exec_pop, exec_rotate, exec_shove, exec_swap,
-};
-use crate::instructions::vector::{
-    string_iterate, vector_boolean_iterate, vector_char_iterate, vector_float_iterate,
-    vector_int_iterate, vector_string_iterate,
-};
+use crate::instructions::registry::INSTRUCTION_REGISTRY;
+use crate::push::erc::instantiate;
 use crate::push::state::Gene;
 use crate::push::state::Gene::StateFunc;
+use crate::push::tag::TAG_EXEC_BLOCKS_OPENED;
 use rand::prelude::*;
 use std::collections::HashMap;
 use std::sync::LazyLock;
 
-/// Generates a random plushy.
+/// Generates a random plushy. ERC genes are replaced with fresh literals.
 pub fn make_random_plushy(
     genes: Vec<Gene>,
     max_init_plushy_size: usize,
@@ -23,39 +16,34 @@ pub fn make_random_plushy(
     let plushy_size = rng.random_range(0..=max_init_plushy_size);
     let mut plushy = Vec::with_capacity(plushy_size);
     for _ in 0..plushy_size {
-        plushy.push(genes[rng.random_range(0..genes.len())].clone());
+        plushy.push(instantiate(
+            genes[rng.random_range(0..genes.len())].clone(),
+            rng,
+        ));
     }
     plushy
 }
 
-/// A map of genes to their number of blocks they open.
+/// A map of genes to their number of blocks they open, taken from the
+/// `blocks_opened` of every registered instruction that opens any. Tag genes
+/// declare theirs in `push::tag`.
 pub static OPEN_MAP: LazyLock<HashMap<Gene, u8>> = LazyLock::new(|| {
-    let mut temp_map = HashMap::default();
-    temp_map.insert(StateFunc(exec_dup), 1u8);
-    temp_map.insert(StateFunc(exec_dup_times), 1u8);
-    temp_map.insert(StateFunc(exec_pop), 1u8);
-    temp_map.insert(StateFunc(exec_rotate), 3u8);
-    temp_map.insert(StateFunc(exec_shove), 1u8);
-    temp_map.insert(StateFunc(exec_swap), 2u8);
-    temp_map.insert(StateFunc(exec_if), 2u8);
-    temp_map.insert(StateFunc(exec_when), 1u8);
-    temp_map.insert(StateFunc(exec_while), 1u8);
-    temp_map.insert(StateFunc(exec_do_while), 1u8);
-    temp_map.insert(StateFunc(exec_do_range), 1u8);
-    temp_map.insert(StateFunc(exec_do_count), 1u8);
-    temp_map.insert(StateFunc(exec_do_times), 1u8);
-    //exec_k, 2
-    //exec_s 3
-    //exec_y 1
-    temp_map.insert(StateFunc(string_iterate), 1u8);
-    temp_map.insert(StateFunc(vector_int_iterate), 1u8);
-    temp_map.insert(StateFunc(vector_float_iterate), 1u8);
-    temp_map.insert(StateFunc(vector_string_iterate), 1u8);
-    temp_map.insert(StateFunc(vector_boolean_iterate), 1u8);
-    temp_map.insert(StateFunc(vector_char_iterate), 1u8);
-    temp_map
+    INSTRUCTION_REGISTRY
+        .all()
+        .iter()
+        .filter(|info| info.blocks_opened > 0)
+        .map(|info| (StateFunc(info.instruction()), info.blocks_opened))
+        .collect()
 });
 
+/// The number of blocks a gene opens, if any.
+fn blocks_opened(gene: &Gene) -> Option<u8> {
+    match gene {
+        Gene::TagExec(_) => Some(TAG_EXEC_BLOCKS_OPENED),
+        _ => OPEN_MAP.get(gene).copied(),
+    }
+}
+
 fn has_openers(genes: &[Gene]) -> bool {
     for gene in genes {
         if is_opener(gene) {
@@ -91,10 +79,10 @@ fn dec_opener(gene: Gene) -> Gene {
 pub fn plushy_to_push(genes: Vec<Gene>) -> Vec<Gene> {
     let mut plushy_buffer: Vec<Gene> = Vec::with_capacity(genes.len() * 2);
     for gene in genes.into_iter() {
-        let open = OPEN_MAP.get(&gene);
+        let open = blocks_opened(&gene);
         plushy_buffer.push(gene);
         if let Some(amt) = open {
-            plushy_buffer.push(Gene::Open(*amt))
+            plushy_buffer.push(Gene::Open(amt))
         }
     }
     let mut push_buffer = Vec::with_capacity(plushy_buffer.len());
@@ -138,8 +126,10 @@ pub fn plushy_to_push(genes: Vec<Gene>) -> Vec<Gene> {
 mod tests {
     use super::*;
     // use crate::instructions::vector::{string_iterate, vector_float_maximum};
+    use crate::instructions::code::{exec_k, exec_s, exec_y};
     use crate::instructions::common::*;
     use crate::instructions::numeric::*;
+    use crate::push::erc::Erc;
     use crate::push::state::*;
     // use crate::push::utils::most_genes;
     // use rand::SeedableRng;
@@ -148,7 +138,7 @@ mod tests {
     fn make_random_plushy_test() {
         // let rng = StdRng::seed_from_u64(42);
         // let rand_plushy = make_random_plushy(most_genes(), 15, rng);
-        // let fin_result = vec![StateFunc(string_iterate), StateFunc(vector_float_maximum)];
+        // let fin_result = vec![StateFunc(instruction!(string_iterate)), StateFunc(instruction!(vector_float_maximum))];
         // Make this consistent later
         // assert_eq!(fin_result, rand_plushy);
     }
@@ -156,39 +146,117 @@ mod tests {
     #[test]
     fn plushy_to_push_test() {
         let plushy = vec![
-            Gene::StateFunc(exec_swap),
-            Gene::StateFunc(float_tan),
-            Gene::StateFunc(int_pop),
+            Gene::StateFunc(instruction!(exec_swap)),
+            Gene::StateFunc(instruction!(float_tan)),
+            Gene::StateFunc(instruction!(int_pop)),
             Gene::Close,
-            Gene::StateFunc(exec_flush),
+            Gene::StateFunc(instruction!(exec_flush)),
             Gene::Close,
-            Gene::StateFunc(boolean_pop),
+            Gene::StateFunc(instruction!(boolean_pop)),
         ];
         let res_push = plushy_to_push(plushy);
         assert_eq!(
             res_push,
             vec![
-                StateFunc(exec_swap),
-                Gene::Block(vec![Gene::StateFunc(float_tan), Gene::StateFunc(int_pop)]),
-                Gene::Block(vec![Gene::StateFunc(exec_flush)]),
-                Gene::StateFunc(boolean_pop),
+                StateFunc(instruction!(exec_swap)),
+                Gene::Block(vec![
+                    Gene::StateFunc(instruction!(float_tan)),
+                    Gene::StateFunc(instruction!(int_pop))
+                ]),
+                Gene::Block(vec![Gene::StateFunc(instruction!(exec_flush))]),
+                Gene::StateFunc(instruction!(boolean_pop)),
             ]
         );
 
         let plushy = vec![
-            Gene::StateFunc(exec_swap),
-            Gene::StateFunc(float_tan),
-            Gene::StateFunc(int_pop),
+            Gene::StateFunc(instruction!(exec_swap)),
+            Gene::StateFunc(instruction!(float_tan)),
+            Gene::StateFunc(instruction!(int_pop)),
             Gene::Close,
         ];
         let res_push = plushy_to_push(plushy);
         assert_eq!(
             res_push,
             vec![
-                StateFunc(exec_swap),
-                Gene::Block(vec![Gene::StateFunc(float_tan), Gene::StateFunc(int_pop)]),
+                StateFunc(instruction!(exec_swap)),
+                Gene::Block(vec![
+                    Gene::StateFunc(instruction!(float_tan)),
+                    Gene::StateFunc(instruction!(int_pop))
+                ]),
                 Gene::Block(vec![]),
             ]
         )
     }
+    #[test]
+    fn open_map_test() {
+        assert_eq!(Some(&2), OPEN_MAP.get(&StateFunc(instruction!(exec_swap))));
+        assert_eq!(Some(&1), OPEN_MAP.get(&StateFunc(instruction!(exec_yank))));
+        assert_eq!(Some(&2), OPEN_MAP.get(&StateFunc(instruction!(exec_k))));
+        assert_eq!(Some(&3), OPEN_MAP.get(&StateFunc(instruction!(exec_s))));
+        assert_eq!(Some(&1), OPEN_MAP.get(&StateFunc(instruction!(exec_y))));
+        assert_eq!(None, OPEN_MAP.get(&StateFunc(instruction!(exec_flush))));
+        assert_eq!(None, OPEN_MAP.get(&StateFunc(instruction!(int_pop))));
+
+        let res_push = plushy_to_push(vec![
+            StateFunc(instruction!(exec_shove_dup)),
+            StateFunc(instruction!(int_pop)),
+            Gene::Close,
+            StateFunc(instruction!(int_add)),
+        ]);
+        assert_eq!(
+            vec![
+                StateFunc(instruction!(exec_shove_dup)),
+                Gene::Block(vec![StateFunc(instruction!(int_pop))]),
+                StateFunc(instruction!(int_add)),
+            ],
+            res_push
+        );
+
+        let res_push = plushy_to_push(vec![Gene::TagExec(3), StateFunc(instruction!(int_pop))]);
+        assert_eq!(
+            vec![
+                Gene::TagExec(3),
+                Gene::Block(vec![StateFunc(instruction!(int_pop))])
+            ],
+            res_push
+        );
+    }
+
+    #[test]
+    fn tag_exec_block_test() {
+        // tag_exec_N opens a single block, closed by the first Close.
+        let res_push = plushy_to_push(vec![
+            Gene::TagExec(7),
+            Gene::GeneInt(1),
+            Gene::Close,
+            Gene::GeneInt(2),
+        ]);
+        assert_eq!(
+            vec![
+                Gene::TagExec(7),
+                Gene::Block(vec![Gene::GeneInt(1)]),
+                Gene::GeneInt(2),
+            ],
+            res_push
+        );
+    }
+    #[test]
+    fn make_random_plushy_erc_test() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let genes = vec![
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::Erc(Erc::Boolean),
+        ];
+        let plushy = make_random_plushy(genes, 50, &mut rng);
+        assert!(
+            plushy
+                .iter()
+                .all(|gene| matches!(gene, Gene::StateFunc(_) | Gene::GeneBoolean(_)))
+        );
+        assert!(
+            plushy
+                .iter()
+                .any(|gene| matches!(gene, Gene::GeneBoolean(_)))
+        );
+    }
 }
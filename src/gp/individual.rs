@@ -1,13 +1,15 @@
-use crate::gp::args::PushArgs;
-use crate::instructions::list::INSTR_NAME_MAP;
+use crate::gp::args::{ErrorFunction, PushArgs, SearchDirection};
+use crate::push::parser::program_to_string;
 use crate::push::state::Gene;
 use polars::prelude::*;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
 
 use super::genome::plushy_to_push;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Individual {
     pub plushy: Vec<Gene>,
     pub push_program: Option<Vec<Gene>>,
@@ -15,36 +17,13 @@ pub struct Individual {
     pub fitness_cases: Option<Vec<Decimal>>,
 }
 
-/// Stringifies a genome. Steps into blocks to recursively stringify too.
-fn rec_gene_stringify(genome: &Vec<Gene>) -> String {
-    let mut final_str: String = "".to_string();
-    for gene in genome {
-        let temp_str: String = match gene {
-            Gene::StateFunc(func) => INSTR_NAME_MAP
-                .get(&(func.clone() as usize))
-                .unwrap()
-                .clone(),
-            Gene::Block(block) => {
-                let mut inner_temp: String = "[ ".to_string();
-                inner_temp.push_str(&rec_gene_stringify(block));
-                inner_temp.push_str("]");
-                inner_temp
-            }
-            other => format!("{:?}", other),
-        };
-        final_str.push_str(&temp_str);
-        final_str.push_str(" ");
-    }
-    final_str
-}
-
 impl fmt::Display for Individual {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut final_string: String = "".to_string();
-        final_string.push_str(&rec_gene_stringify(&self.plushy));
+        final_string.push_str(&program_to_string(&self.plushy));
         final_string.push_str("\n------------------------------------------------\n");
         if let Some(program) = &self.push_program {
-            final_string.push_str(&rec_gene_stringify(program))
+            final_string.push_str(&program_to_string(program))
         } else {
             final_string.push_str("No push program")
         }
@@ -64,20 +43,69 @@ impl fmt::Display for Individual {
     }
 }
 
+/// The error given to every case of a program that couldn't be evaluated. This
+/// is the worst error for the search direction, `error_penalty` when
+/// minimizing and its negation when maximizing.
+fn penalty(push_args: &PushArgs) -> Decimal {
+    match push_args.search_direction {
+        SearchDirection::Min => push_args.error_penalty,
+        SearchDirection::Max => -push_args.error_penalty,
+    }
+}
+
+/// Runs an error function on a push program. An error function returns one
+/// error per row of `data`, so a program that can't be evaluated gets that
+/// many penalty errors instead. This happens when the interpreter can't run
+/// the program or the evaluation panics. Both are logged with the program,
+/// since they point to a bug rather than a weak program, and the run carries
+/// on without the offending individual.
+pub fn evaluate(
+    error_func: ErrorFunction,
+    push_args: &PushArgs,
+    data: &DataFrame,
+    push_program: Vec<Gene>,
+) -> Vec<Decimal> {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        error_func(push_args, data, push_program.clone())
+    }));
+    match result {
+        Ok(Ok(error_vec)) => {
+            assert_eq!(
+                data.height(),
+                error_vec.len(),
+                "Error function must return one error per row of data"
+            );
+            error_vec
+        }
+        Ok(Err(err)) => {
+            eprintln!(
+                "Evaluation failed with \"{}\" on program: {}",
+                err,
+                program_to_string(&push_program)
+            );
+            vec![penalty(push_args); data.height()]
+        }
+        Err(_) => {
+            eprintln!(
+                "Evaluation panicked on program: {}",
+                program_to_string(&push_program)
+            );
+            vec![penalty(push_args); data.height()]
+        }
+    }
+}
+
 impl Individual {
     // Creates a new individual based off a plushy. Converts it to a push program
     // and runs the error function on it.
-    pub fn with_error<F>(
+    pub fn with_error(
         plushy: Vec<Gene>,
-        error_func: F,
+        error_func: ErrorFunction,
         push_args: &PushArgs,
         data: &DataFrame,
-    ) -> Self
-    where
-        F: Fn(&PushArgs, &DataFrame, Vec<Gene>) -> Vec<Decimal>,
-    {
+    ) -> Self {
         let push_program = Some(plushy_to_push(plushy.clone()));
-        let error_vec = error_func(push_args, data, push_program.clone().unwrap());
+        let error_vec = evaluate(error_func, push_args, data, push_program.clone().unwrap());
         Self {
             plushy,
             push_program,
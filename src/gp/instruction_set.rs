@@ -0,0 +1,142 @@
+//! # Instruction Sets
+//!
+//! Builds the genes a run evolves with from the stacks a problem uses, like
+//! PushGP's `get-stack-instructions`. On top of the instructions for those
+//! stacks, instructions can be added or removed by name with glob patterns
+//! such as `exec_*` or `int_yank?dup`. `Close`, `Skip`, and a `Place` for
+//! every input column of the data are always part of the set.
+
+use crate::instructions::list::instructions_for;
+use crate::instructions::registry::INSTRUCTION_REGISTRY;
+use crate::push::state::{Gene, Stack};
+use glob::Pattern;
+use polars::prelude::*;
+
+/// Builds an instruction set for a problem.
+///
+/// `include` adds every registered instruction whose name matches one of its
+/// patterns, even ones outside `stacks`. `exclude` then removes every
+/// instruction whose name matches one of its patterns. Every column of `data`
+/// not named in `output_columns` is an input, and gets a `Place` in column
+/// order. Panics on an invalid pattern.
+pub fn instruction_set(
+    stacks: &[Stack],
+    include: &[&str],
+    exclude: &[&str],
+    data: &DataFrame,
+    output_columns: &[&str],
+) -> Vec<Gene> {
+    let include = patterns(include);
+    let exclude = patterns(exclude);
+
+    let mut genes = instructions_for(stacks);
+    for info in INSTRUCTION_REGISTRY.all() {
+        let gene = Gene::StateFunc(info.instruction());
+        if matches_any(&include, info.name) && !genes.contains(&gene) {
+            genes.push(gene);
+        }
+    }
+    genes.retain(|gene| match gene {
+        Gene::StateFunc(instr) => !matches_any(&exclude, instr.name),
+        _ => true,
+    });
+
+    genes.push(Gene::Close);
+    genes.push(Gene::Skip);
+    genes.extend((0..input_count(data, output_columns)).map(Gene::Place));
+    genes
+}
+
+/// The number of columns in `data` not named in `output_columns`.
+fn input_count(data: &DataFrame, output_columns: &[&str]) -> usize {
+    data.get_column_names()
+        .iter()
+        .filter(|name| !output_columns.contains(&name.as_str()))
+        .count()
+}
+
+fn patterns(globs: &[&str]) -> Vec<Pattern> {
+    globs
+        .iter()
+        .map(|glob| {
+            Pattern::new(glob).unwrap_or_else(|err| panic!("Invalid pattern {}: {}", glob, err))
+        })
+        .collect()
+}
+
+fn matches_any(patterns: &[Pattern], name: &str) -> bool {
+    patterns.iter().any(|pattern| pattern.matches(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::code::exec_if;
+    use crate::instructions::common::{code_from_int, exec_dup, int_dup, int_yank};
+    use crate::instructions::logical::boolean_and;
+    use crate::instructions::numeric::{float_add, int_add, int_from_boolean, int_from_float};
+
+    fn data() -> DataFrame {
+        df!(
+            "x0" => [1, 2],
+            "x1" => [3, 4],
+            "y" => [4, 6],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn instructions_for_test() {
+        let genes = instructions_for(&[Stack::Int, Stack::Boolean, Stack::Exec]);
+        for instr in [
+            instruction!(int_add),
+            instruction!(int_from_boolean),
+            instruction!(boolean_and),
+            instruction!(exec_dup),
+            instruction!(exec_if),
+        ] {
+            assert!(genes.contains(&Gene::StateFunc(instr)));
+        }
+        // Touch a stack that isn't in the list.
+        for instr in [
+            instruction!(float_add),
+            instruction!(int_from_float),
+            instruction!(code_from_int),
+        ] {
+            assert!(!genes.contains(&Gene::StateFunc(instr)));
+        }
+        assert!(instructions_for(&[]).is_empty());
+    }
+
+    #[test]
+    fn instruction_set_test() {
+        let genes = instruction_set(&[Stack::Int], &[], &[], &data(), &["y"]);
+        assert_eq!(
+            &[Gene::Close, Gene::Skip, Gene::Place(0), Gene::Place(1)],
+            &genes[genes.len() - 4..]
+        );
+        assert!(genes.contains(&Gene::StateFunc(instruction!(int_add))));
+        assert!(!genes.contains(&Gene::StateFunc(instruction!(exec_dup))));
+
+        let genes = instruction_set(
+            &[Stack::Int],
+            &["exec_dup", "float_a?d"],
+            &["int_*dup*", "int_yank"],
+            &data(),
+            &["x1", "y"],
+        );
+        assert!(genes.contains(&Gene::StateFunc(instruction!(exec_dup))));
+        assert!(genes.contains(&Gene::StateFunc(instruction!(float_add))));
+        assert!(genes.contains(&Gene::StateFunc(instruction!(int_add))));
+        assert!(!genes.contains(&Gene::StateFunc(instruction!(int_dup))));
+        assert!(!genes.contains(&Gene::StateFunc(instruction!(int_yank))));
+        assert_eq!(Some(&Gene::Place(0)), genes.last());
+        assert!(!genes.contains(&Gene::Place(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid pattern")]
+    fn invalid_pattern_test() {
+        instruction_set(&[Stack::Int], &["int_[add"], &[], &data(), &["y"]);
+    }
+}
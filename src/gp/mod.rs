@@ -1,103 +1,333 @@
-use crate::gp::genome::make_random_plushy;
-use args::{PushArgs, SearchDirection};
-use individual::Individual;
-use rust_decimal::dec;
+use args::{DownsampleType, PushArgs, SearchDirection};
+use checkpoint::{Checkpoint, CheckpointError};
+use downsample::{
+    case_distances, downsample_data, initial_case_distances, select_downsample_maxmin,
+    select_downsample_random,
+};
+use individual::{Individual, evaluate};
+use polars::prelude::*;
+use population::{initial_population, next_generation, sort_population};
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use reporter::{Reporter, StdoutReporter};
+use result::{GenerationSummary, RunResult};
+use rust_decimal::Decimal;
 use simplification::auto_simplify_plushy;
-use std::iter::repeat;
-use variation::new_individual;
 
 pub mod args;
+pub mod checkpoint;
+pub mod downsample;
 pub mod genome;
 pub mod individual;
+pub mod instruction_set;
+pub mod population;
+pub mod reporter;
+pub mod result;
 pub mod selection;
 pub mod simplification;
+pub mod stats;
 pub mod utils;
 pub mod variation;
 
-pub fn gp_loop(push_args: PushArgs) {
-    let mut rng = rand::rng();
+/// Makes the rng used for one generation of a run. Generation 0 builds the
+/// initial population. Deriving each generation's rng from the seed rather
+/// than carrying a single rng through the run means any generation can be
+/// reproduced from the seed and the generation number alone.
+pub fn generation_rng(seed: u64, generation: usize) -> StdRng {
+    StdRng::seed_from_u64(seed ^ (generation as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
 
-    // Population is evaluated in the Individual creation.
-    let mut pop: Vec<Individual> = repeat(Individual::with_error(
-        make_random_plushy(
-            push_args.instructions.clone().unwrap(),
-            push_args.max_init_plushy_size,
-            &mut rng,
-        ),
-        push_args.error_function.unwrap(),
-        &push_args,
-        &push_args.training_data.clone().unwrap(),
-    ))
-    .take(push_args.pop_size)
-    .collect::<Vec<_>>();
-    // Need to sort it here tho
+/// Picks the cases a generation is evaluated on. This is all of the
+/// training data unless downsampling.
+fn select_eval_data(
+    push_args: &PushArgs,
+    case_distances: &[Vec<usize>],
+    rng: &mut impl Rng,
+) -> DataFrame {
+    let training_data = push_args.training_data.as_ref().unwrap();
+    if !push_args.downsample {
+        return training_data.clone();
+    }
+    let cases = match push_args.downsample_type {
+        DownsampleType::Random => {
+            select_downsample_random(training_data.height(), push_args.downsample_rate, rng)
+        }
+        DownsampleType::Informed => {
+            select_downsample_maxmin(case_distances, push_args.downsample_rate, rng)
+        }
+    };
+    downsample_data(training_data, &cases)
+}
+
+/// Evaluates a sample of the parents on all of the training cases and
+/// measures the distances between cases from their errors.
+fn update_case_distances(
+    pop: &[Individual],
+    push_args: &PushArgs,
+    rng: &mut impl Rng,
+) -> Vec<Vec<usize>> {
+    let training_data = push_args.training_data.as_ref().unwrap();
+    let error_func = push_args.error_function.unwrap();
+    let sample_size =
+        ((pop.len() as f64 * push_args.ds_parent_rate).round() as usize).clamp(1, pop.len());
+    let parents: Vec<&Individual> = pop.choose_multiple(rng, sample_size).collect();
+    let eval = |ind: &&Individual| {
+        evaluate(
+            error_func,
+            push_args,
+            training_data,
+            ind.push_program.clone().unwrap(),
+        )
+    };
+    let errors: Vec<Vec<Decimal>> = if push_args.use_single_thread {
+        parents.iter().map(eval).collect()
+    } else {
+        parents.par_iter().map(eval).collect()
+    };
+    case_distances(&errors, push_args.search_direction)
+}
+
+/// The best individual of a sorted population with its errors on all of the
+/// training cases. When downsampling, the population only has errors on the
+/// cases of its generation, so the individual is evaluated again.
+fn full_data_best(pop: &[Individual], push_args: &PushArgs) -> Individual {
+    if push_args.downsample {
+        Individual::with_error(
+            pop[0].plushy.clone(),
+            push_args.error_function.unwrap(),
+            push_args,
+            push_args.training_data.as_ref().unwrap(),
+        )
+    } else {
+        pop[0].clone()
+    }
+}
+
+/// Tells the reporter when the best individual of a generation, evaluated on
+/// all of the training cases, is a solution. Returns whether it is one.
+fn report_solution(
+    ind: &Individual,
+    generation: usize,
+    push_args: &PushArgs,
+    reporter: &mut dyn Reporter,
+) -> bool {
+    let solved = within_threshold(ind.total_fitness.unwrap(), push_args);
+    if solved {
+        reporter.on_solution_found(generation, ind);
+    }
+    solved
+}
+
+/// Whether total fitness `a` is better than `b`.
+fn is_better(a: Decimal, b: Decimal, push_args: &PushArgs) -> bool {
     match push_args.search_direction {
-        SearchDirection::Min => pop.sort_by(|ind0, ind1| {
-            ind0.total_fitness
-                .unwrap()
-                .cmp(&ind1.total_fitness.unwrap())
-        }),
-        SearchDirection::Max => pop.sort_by(|ind0, ind1| {
-            ind1.total_fitness
-                .unwrap()
-                .cmp(&ind0.total_fitness.unwrap())
-        }),
+        SearchDirection::Min => a < b,
+        SearchDirection::Max => a > b,
     }
+}
 
-    let mut generation: usize = 0;
-    let mut best_ind = pop[0].clone();
+/// Checks a total fitness against `solution_error_threshold`. For maximization
+/// problems the total fitness must be at least the threshold.
+fn within_threshold(total_fitness: Decimal, push_args: &PushArgs) -> bool {
+    match push_args.search_direction {
+        SearchDirection::Min => total_fitness <= push_args.solution_error_threshold,
+        SearchDirection::Max => total_fitness >= push_args.solution_error_threshold,
+    }
+}
 
-    println!("Starting run!");
+/// Evaluates an individual on the testing data, if there is any.
+fn test_errors(ind: &Individual, push_args: &PushArgs) -> Option<Vec<Decimal>> {
+    push_args.testing_data.as_ref().map(|testing_data| {
+        evaluate(
+            push_args.error_function.unwrap(),
+            push_args,
+            testing_data,
+            ind.push_program.clone().unwrap(),
+        )
+    })
+}
 
-    while generation < push_args.max_generations
-        && (&pop[0].total_fitness.unwrap() != &dec!(0.0) || push_args.dont_end)
-    {
-        // Create new children and evaluate them too
-        // May have to remove these repeats later for multithreading.
-        // Could probably make own repeat function that does this but
-        // multi threaded.
-        pop = if push_args.elitism {
-            let mut ret_vec = repeat(new_individual(pop, &push_args, &mut rng))
-                .take(push_args.pop_size - 1)
-                .collect::<Vec<_>>();
-            ret_vec.insert(0, best_ind.clone());
-            ret_vec
-        } else {
-            repeat(new_individual(pop, &push_args, &mut rng))
-                .take(push_args.pop_size)
-                .collect()
-        };
-        match push_args.search_direction {
-            SearchDirection::Min => pop.sort_by(|ind0, ind1| {
-                ind0.total_fitness
-                    .unwrap()
-                    .cmp(&ind1.total_fitness.unwrap())
-            }),
-            SearchDirection::Max => pop.sort_by(|ind0, ind1| {
-                ind1.total_fitness
-                    .unwrap()
-                    .cmp(&ind0.total_fitness.unwrap())
-            }),
+/// Runs PushGP with the given arguments and returns what the run produced.
+/// Fails only when a checkpoint can't be written.
+pub fn gp_loop(push_args: PushArgs) -> Result<RunResult, CheckpointError> {
+    gp_loop_with_reporter(push_args, &mut StdoutReporter)
+}
+
+/// Runs PushGP like `gp_loop`, telling `reporter` about the progress of the run.
+pub fn gp_loop_with_reporter(
+    push_args: PushArgs,
+    reporter: &mut dyn Reporter,
+) -> Result<RunResult, CheckpointError> {
+    run(push_args, None, reporter)
+}
+
+/// Continues a run from a checkpoint. `push_args` must be the arguments the
+/// checkpointed run was started with, though `max_generations` may change.
+/// Fails when they don't match the checkpoint or a checkpoint can't be written.
+pub fn resume_gp_loop(
+    push_args: PushArgs,
+    checkpoint: Checkpoint,
+    reporter: &mut dyn Reporter,
+) -> Result<RunResult, CheckpointError> {
+    checkpoint.check_args(&push_args)?;
+    run(push_args, Some(checkpoint), reporter)
+}
+
+/// Whether the run measures case distances for informed downsampling.
+fn is_informed(push_args: &PushArgs) -> bool {
+    push_args.downsample && matches!(push_args.downsample_type, DownsampleType::Informed)
+}
+
+/// Creates and evaluates the initial population, packed as a checkpoint
+/// for generation 0.
+fn initial_checkpoint(push_args: &PushArgs, seed: u64) -> Checkpoint {
+    let mut rng = generation_rng(seed, 0);
+    let case_count = push_args.training_data.as_ref().unwrap().height();
+    let eval_data = select_eval_data(push_args, &initial_case_distances(case_count), &mut rng);
+
+    // Population is evaluated in the Individual creation.
+    let mut pop = initial_population(push_args, &eval_data, &mut rng);
+    sort_population(&mut pop, push_args.search_direction);
+    Checkpoint {
+        seed,
+        generation: 0,
+        pop_size: push_args.pop_size,
+        best_individual: full_data_best(&pop, push_args),
+        case_distances: vec![],
+        population: pop,
+    }
+}
+
+/// Runs PushGP from a checkpoint, or from a fresh population when there is none.
+fn run(
+    push_args: PushArgs,
+    checkpoint: Option<Checkpoint>,
+    reporter: &mut dyn Reporter,
+) -> Result<RunResult, CheckpointError> {
+    let seed = match &checkpoint {
+        Some(checkpoint) => checkpoint.seed,
+        None => push_args.seed.unwrap_or_else(|| rand::rng().random()),
+    };
+    reporter.on_run_start(&push_args, seed);
+    // The run that saved a checkpoint already reported its generation.
+    let resumed = checkpoint.is_some();
+    let Checkpoint {
+        mut generation,
+        population: mut pop,
+        best_individual: mut best_ind,
+        case_distances,
+        ..
+    } = checkpoint.unwrap_or_else(|| initial_checkpoint(&push_args, seed));
+
+    let training_data = push_args.training_data.as_ref().unwrap();
+    let mut distances = if case_distances.is_empty() {
+        initial_case_distances(training_data.height())
+    } else {
+        case_distances
+    };
+    let mut generation_summaries = vec![];
+    let mut solved = if resumed {
+        // A solution was reported if the best individual so far is one.
+        within_threshold(best_ind.total_fitness.unwrap(), &push_args)
+    } else {
+        let summary =
+            GenerationSummary::from_sorted_pop(generation, &pop, push_args.search_direction);
+        reporter.on_generation_end(&summary, &pop);
+        generation_summaries.push(summary);
+        // The best individual of the initial population is the best so far.
+        report_solution(&best_ind, generation, &push_args, reporter)
+    };
+
+    while generation < push_args.max_generations && (push_args.dont_end || !solved) {
+        let mut rng = generation_rng(seed, generation + 1);
+
+        if is_informed(&push_args) && generation.is_multiple_of(push_args.ds_parent_gens.max(1)) {
+            distances = update_case_distances(&pop, &push_args, &mut rng);
         }
-        best_ind = pop[0].clone();
+        let eval_data = select_eval_data(&push_args, &distances, &mut rng);
+
+        // Create new children and evaluate them too
+        pop = next_generation(&pop, &push_args, &eval_data, &mut rng);
+        sort_population(&mut pop, push_args.search_direction);
         generation += 1;
+        let mut summary =
+            GenerationSummary::from_sorted_pop(generation, &pop, push_args.search_direction);
 
-        println!("Generation: {}", generation);
-        println!("Best Individual: {}", best_ind);
-        // println!("Worst Individual: {}", pop[pop.len() - 1]);
+        // Compared on all of the training cases, since the downsample changes
+        // every generation.
+        let gen_best = full_data_best(&pop, &push_args);
+        if is_better(
+            gen_best.total_fitness.unwrap(),
+            best_ind.total_fitness.unwrap(),
+            &push_args,
+        ) {
+            best_ind = gen_best.clone();
+            if push_args.test_new_best
+                && let Some(errors) = test_errors(&best_ind, &push_args)
+            {
+                summary.best_test_total_fitness = Some(errors.iter().sum());
+            }
+        }
+        reporter.on_generation_end(&summary, &pop);
+        generation_summaries.push(summary);
+        if !solved {
+            solved = report_solution(&gen_best, generation, &push_args, reporter);
+        }
+
+        if let Some(path) = &push_args.checkpoint_path
+            && generation.is_multiple_of(push_args.checkpoint_gens.max(1))
+        {
+            let checkpoint = Checkpoint {
+                seed,
+                generation,
+                pop_size: push_args.pop_size,
+                best_individual: best_ind.clone(),
+                case_distances: if is_informed(&push_args) {
+                    distances.clone()
+                } else {
+                    vec![]
+                },
+                population: pop.clone(),
+            };
+            checkpoint.save(path)?;
+        }
     }
 
-    let simplified_plushy = auto_simplify_plushy(
-        best_ind.plushy,
-        push_args.error_function.unwrap(),
-        &push_args,
-    );
-    let simplified_ind = Individual::with_error(
-        simplified_plushy,
-        push_args.error_function.unwrap(),
-        &push_args.clone(),
-        &push_args.training_data.unwrap(),
-    );
-
-    println!("Simplified Best Individual: {}", simplified_ind);
+    let simplified_ind = if push_args.use_simplification {
+        // Simplification draws from the rng of the generation after the last one.
+        let simplified_plushy = auto_simplify_plushy(
+            best_ind.plushy.clone(),
+            push_args.error_function.unwrap(),
+            &push_args,
+            &mut generation_rng(seed, generation + 1),
+        );
+        let simplified_ind = Individual::with_error(
+            simplified_plushy,
+            push_args.error_function.unwrap(),
+            &push_args,
+            training_data,
+        );
+        Some(simplified_ind)
+    } else {
+        None
+    };
+
+    let best_test_errors = test_errors(&best_ind, &push_args);
+    let simplified_test_errors = simplified_ind
+        .as_ref()
+        .and_then(|ind| test_errors(ind, &push_args));
+
+    let result = RunResult {
+        solution_found: within_threshold(best_ind.total_fitness.unwrap(), &push_args),
+        best_individual: best_ind,
+        simplified_individual: simplified_ind,
+        generations: generation,
+        generation_summaries,
+        test_errors: best_test_errors,
+        simplified_test_errors,
+    };
+    reporter.on_run_end(&result);
+    Ok(result)
 }
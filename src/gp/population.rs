@@ -0,0 +1,209 @@
+//! # Population
+//!
+//! Creates the initial population and every generation after it. Each
+//! individual is made and evaluated on its own, either on one thread or on
+//! the thread pool.
+
+use crate::gp::args::{PushArgs, SearchDirection};
+use crate::gp::genome::make_random_plushy;
+use crate::gp::individual::Individual;
+use crate::gp::variation::new_individual;
+use polars::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+
+/// Builds `count` individuals with `make_ind`. Each individual gets its own
+/// rng seeded from `rng` before any work starts, so the results are the same
+/// whether the individuals are built on one thread or on the thread pool.
+fn build_individuals<F>(
+    count: usize,
+    push_args: &PushArgs,
+    rng: &mut impl Rng,
+    make_ind: F,
+) -> Vec<Individual>
+where
+    F: Fn(&mut StdRng) -> Individual + Sync,
+{
+    let seeds: Vec<u64> = (0..count).map(|_| rng.random()).collect();
+    if push_args.use_single_thread {
+        seeds
+            .into_iter()
+            .map(|seed| make_ind(&mut StdRng::seed_from_u64(seed)))
+            .collect()
+    } else {
+        seeds
+            .into_par_iter()
+            .map(|seed| make_ind(&mut StdRng::seed_from_u64(seed)))
+            .collect()
+    }
+}
+
+/// Creates `pop_size` random individuals evaluated on `data`.
+pub fn initial_population(
+    push_args: &PushArgs,
+    data: &DataFrame,
+    rng: &mut impl Rng,
+) -> Vec<Individual> {
+    build_individuals(push_args.pop_size, push_args, rng, |ind_rng| {
+        Individual::with_error(
+            make_random_plushy(
+                push_args.gene_pool(),
+                push_args.max_init_plushy_size,
+                ind_rng,
+            ),
+            push_args.error_function.unwrap(),
+            push_args,
+            data,
+        )
+    })
+}
+
+/// Creates the next generation from a population sorted best first. Every
+/// child is evaluated on `data`. With elitism the best individual carries
+/// over as the first member, re-evaluated on `data` when downsampling since
+/// its errors are on the previous generation's cases.
+pub fn next_generation(
+    pop: &[Individual],
+    push_args: &PushArgs,
+    data: &DataFrame,
+    rng: &mut impl Rng,
+) -> Vec<Individual> {
+    let child_count = if push_args.elitism {
+        push_args.pop_size - 1
+    } else {
+        push_args.pop_size
+    };
+    let mut children = build_individuals(child_count, push_args, rng, |ind_rng| {
+        new_individual(pop, push_args, data, ind_rng)
+    });
+    if push_args.elitism {
+        let elite = if push_args.downsample {
+            Individual::with_error(
+                pop[0].plushy.clone(),
+                push_args.error_function.unwrap(),
+                push_args,
+                data,
+            )
+        } else {
+            pop[0].clone()
+        };
+        children.insert(0, elite);
+    }
+    children
+}
+
+/// Sorts a population so the best individual comes first.
+pub fn sort_population(pop: &mut [Individual], direction: SearchDirection) {
+    match direction {
+        SearchDirection::Min => pop.sort_by(|ind0, ind1| {
+            ind0.total_fitness
+                .unwrap()
+                .cmp(&ind1.total_fitness.unwrap())
+        }),
+        SearchDirection::Max => pop.sort_by(|ind0, ind1| {
+            ind1.total_fitness
+                .unwrap()
+                .cmp(&ind0.total_fitness.unwrap())
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::push::interpreter::InterpreterError;
+    use crate::push::state::Gene;
+    use crate::push::utils::most_genes;
+    use rust_decimal::Decimal;
+    use std::collections::HashSet;
+
+    fn len_error_function(
+        _push_args: &PushArgs,
+        _data: &DataFrame,
+        push_program: Vec<Gene>,
+    ) -> Result<Vec<Decimal>, InterpreterError> {
+        Ok(vec![Decimal::from(push_program.len())])
+    }
+
+    fn const_error_function(
+        _push_args: &PushArgs,
+        _data: &DataFrame,
+        _push_program: Vec<Gene>,
+    ) -> Result<Vec<Decimal>, InterpreterError> {
+        Ok(vec![Decimal::ZERO])
+    }
+
+    fn test_args() -> PushArgs {
+        let mut args = PushArgs::new();
+        args.training_data = Some(df!("y" => [0]).unwrap());
+        args.instructions = Some(most_genes());
+        args.error_function = Some(len_error_function);
+        args.pop_size = 50;
+        args
+    }
+
+    fn distinct_plushies(pop: &[Individual]) -> usize {
+        pop.iter()
+            .map(|ind| &ind.plushy)
+            .collect::<HashSet<&Vec<Gene>>>()
+            .len()
+    }
+
+    #[test]
+    fn build_individuals_threading_test() {
+        let args = test_args();
+        let make_ind = |ind_rng: &mut StdRng| {
+            Individual::with_error(
+                make_random_plushy(most_genes(), 20, ind_rng),
+                len_error_function,
+                &args,
+                args.training_data.as_ref().unwrap(),
+            )
+        };
+
+        let mut single_args = args.clone();
+        single_args.use_single_thread = true;
+        let single = build_individuals(50, &single_args, &mut StdRng::seed_from_u64(42), make_ind);
+
+        let mut multi_args = args.clone();
+        multi_args.use_single_thread = false;
+        let multi = build_individuals(50, &multi_args, &mut StdRng::seed_from_u64(42), make_ind);
+
+        assert_eq!(50, single.len());
+        assert_eq!(single, multi);
+    }
+
+    #[test]
+    fn initial_population_diversity_test() {
+        let args = test_args();
+        let pop = initial_population(
+            &args,
+            args.training_data.as_ref().unwrap(),
+            &mut StdRng::seed_from_u64(42),
+        );
+        assert_eq!(50, pop.len());
+        // Each plushy is made on its own, not cloned from one individual.
+        assert!(distinct_plushies(&pop) > 1);
+    }
+
+    #[test]
+    fn next_generation_test() {
+        let mut args = test_args();
+        // Every individual ties so selection doesn't always pick the same parent.
+        args.error_function = Some(const_error_function);
+        let data = args.training_data.clone().unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut pop = initial_population(&args, &data, &mut rng);
+        sort_population(&mut pop, args.search_direction);
+
+        let children = next_generation(&pop, &args, &data, &mut rng);
+        assert_eq!(50, children.len());
+        assert!(distinct_plushies(&children) > 1);
+
+        args.elitism = true;
+        let children = next_generation(&pop, &args, &data, &mut rng);
+        assert_eq!(50, children.len());
+        assert_eq!(pop[0], children[0]);
+    }
+}
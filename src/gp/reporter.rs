@@ -0,0 +1,203 @@
+//! # Reporters
+//!
+//! A reporter gets called by `gp_loop` as a run progresses. Every hook has an
+//! empty default so a reporter only implements the hooks it cares about.
+
+use crate::gp::args::PushArgs;
+use crate::gp::individual::Individual;
+use crate::gp::result::{GenerationSummary, RunResult};
+use rust_decimal::Decimal;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Hooks into the progress of a run.
+pub trait Reporter {
+    /// Called once before the initial population is made.
+    fn on_run_start(&mut self, _push_args: &PushArgs, _seed: u64) {}
+
+    /// Called after every generation, including the initial population.
+    /// The population is sorted best first.
+    fn on_generation_end(&mut self, _summary: &GenerationSummary, _pop: &[Individual]) {}
+
+    /// Called the first time the best individual of a generation is a solution.
+    fn on_solution_found(&mut self, _generation: usize, _ind: &Individual) {}
+
+    /// Called once the run is finished.
+    fn on_run_end(&mut self, _result: &RunResult) {}
+}
+
+/// Prints the progress of a run to stdout.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StdoutReporter;
+
+impl Reporter for StdoutReporter {
+    fn on_run_start(&mut self, _push_args: &PushArgs, seed: u64) {
+        println!("Starting run with seed: {}", seed);
+    }
+
+    fn on_generation_end(&mut self, summary: &GenerationSummary, pop: &[Individual]) {
+        if summary.generation == 0 {
+            return;
+        }
+        println!("Generation: {}", summary.generation);
+        println!("Best Individual: {}", pop[0]);
+        if let Some(test_total_fitness) = summary.best_test_total_fitness {
+            println!("New Best Test Total Error: {}", test_total_fitness);
+        }
+    }
+
+    fn on_solution_found(&mut self, generation: usize, _ind: &Individual) {
+        println!("Solution found in generation: {}", generation);
+    }
+
+    fn on_run_end(&mut self, result: &RunResult) {
+        if let Some(simplified_ind) = &result.simplified_individual {
+            println!("Simplified Best Individual: {}", simplified_ind);
+        }
+        if let Some(errors) = &result.test_errors {
+            println!(
+                "Best Individual Test Total Error: {}",
+                errors.iter().sum::<Decimal>()
+            );
+        }
+        if let Some(errors) = &result.simplified_test_errors {
+            println!(
+                "Simplified Best Individual Test Total Error: {}",
+                errors.iter().sum::<Decimal>()
+            );
+        }
+    }
+}
+
+/// Reports nothing.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QuietReporter;
+
+impl Reporter for QuietReporter {}
+
+/// Writes the summary of every generation to a file as one JSON object per line.
+/// A failed write stops any further writes, the run itself goes on. The
+/// error is kept for `error`.
+pub struct JsonLinesReporter<W: Write> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl JsonLinesReporter<BufWriter<File>> {
+    /// Creates the file at `path`, truncating it if it exists.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> JsonLinesReporter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    /// The error that stopped the reporter writing, if any.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Gives back the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Runs `write` unless an earlier write failed, keeping its error if it fails.
+    fn try_write(&mut self, write: impl FnOnce(&mut W) -> io::Result<()>) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(err) = write(&mut self.writer) {
+            self.error = Some(err);
+        }
+    }
+}
+
+impl<W: Write> Reporter for JsonLinesReporter<W> {
+    fn on_generation_end(&mut self, summary: &GenerationSummary, _pop: &[Individual]) {
+        self.try_write(|writer| {
+            serde_json::to_writer(&mut *writer, summary)?;
+            writeln!(writer)
+        });
+    }
+
+    fn on_run_end(&mut self, _result: &RunResult) {
+        self.try_write(|writer| writer.flush());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gp::args::SearchDirection;
+    use crate::push::state::Gene;
+    use rust_decimal::dec;
+
+    #[test]
+    fn summary_json_test() {
+        let pop = vec![
+            Individual {
+                plushy: vec![Gene::GeneInt(1)],
+                push_program: Some(vec![Gene::GeneInt(1)]),
+                total_fitness: Some(dec!(1.5)),
+                fitness_cases: Some(vec![dec!(0.5), dec!(1.0)]),
+            },
+            Individual {
+                plushy: vec![Gene::GeneInt(1), Gene::GeneInt(2), Gene::GeneInt(3)],
+                push_program: Some(vec![Gene::GeneInt(1), Gene::GeneInt(2), Gene::GeneInt(3)]),
+                total_fitness: Some(dec!(2.5)),
+                fitness_cases: Some(vec![dec!(2.0), dec!(0.5)]),
+            },
+        ];
+        let mut summary = GenerationSummary::from_sorted_pop(3, &pop, SearchDirection::Min);
+        assert_eq!(
+            "{\"generation\":3,\"best_total_fitness\":1.5,\"average_total_fitness\":2.0,\"worst_total_fitness\":2.5,\"best_plushy_size\":1,\"best_test_total_fitness\":null,\"total_fitness\":{\"min\":1.5,\"max\":2.5,\"mean\":2.0,\"median\":2.0},\"plushy_size\":{\"min\":1.0,\"max\":3.0,\"mean\":2.0,\"median\":2.0},\"push_program_size\":{\"min\":1.0,\"max\":3.0,\"mean\":2.0,\"median\":2.0},\"behavioral_diversity\":1.0,\"genotypic_diversity\":1.0,\"lexicase_elite_counts\":[1,1]}",
+            serde_json::to_string(&summary).unwrap()
+        );
+
+        summary.best_test_total_fitness = Some(dec!(2.0));
+        assert!(
+            serde_json::to_string(&summary)
+                .unwrap()
+                .contains("\"best_test_total_fitness\":2.0,")
+        );
+    }
+
+    /// Fails every write.
+    struct BrokenWriter;
+
+    impl Write for BrokenWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Err(io::Error::other("disk full"))
+        }
+    }
+
+    #[test]
+    fn json_lines_write_error_test() {
+        let pop = vec![Individual {
+            plushy: vec![],
+            push_program: Some(vec![]),
+            total_fitness: Some(dec!(0.0)),
+            fitness_cases: Some(vec![dec!(0.0)]),
+        }];
+        let summary = GenerationSummary::from_sorted_pop(0, &pop, SearchDirection::Min);
+
+        let mut reporter = JsonLinesReporter::new(BrokenWriter);
+        assert!(reporter.error().is_none());
+        // Doesn't panic, the error is kept instead.
+        reporter.on_generation_end(&summary, &pop);
+        reporter.on_generation_end(&summary, &pop);
+        assert_eq!("disk full", reporter.error().unwrap().to_string());
+    }
+}
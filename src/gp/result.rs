@@ -0,0 +1,55 @@
+use crate::gp::args::SearchDirection;
+use crate::gp::individual::Individual;
+use crate::gp::stats::PopulationStats;
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+/// A summary of a single generation of a run. Serialized flat, with the
+/// fitnesses as numbers.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct GenerationSummary {
+    pub generation: usize,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub best_total_fitness: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub average_total_fitness: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub worst_total_fitness: Decimal,
+    pub best_plushy_size: usize,
+    #[serde(with = "rust_decimal::serde::float_option")]
+    pub best_test_total_fitness: Option<Decimal>, // Only when test_new_best is set and a new best appeared
+    #[serde(flatten)]
+    pub stats: PopulationStats, // Statistics over the whole population
+}
+
+impl GenerationSummary {
+    /// Summarizes a population sorted best first.
+    pub fn from_sorted_pop(
+        generation: usize,
+        pop: &[Individual],
+        direction: SearchDirection,
+    ) -> Self {
+        let total: Decimal = pop.iter().map(|ind| ind.total_fitness.unwrap()).sum();
+        Self {
+            generation,
+            best_total_fitness: pop[0].total_fitness.unwrap(),
+            average_total_fitness: total / Decimal::from(pop.len()),
+            worst_total_fitness: pop[pop.len() - 1].total_fitness.unwrap(),
+            best_plushy_size: pop[0].plushy.len(),
+            best_test_total_fitness: None,
+            stats: PopulationStats::from_pop(pop, direction),
+        }
+    }
+}
+
+/// Everything a finished run produced.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunResult {
+    pub best_individual: Individual, // Best individual of the run, evaluated on all training cases
+    pub simplified_individual: Option<Individual>, // The best individual after simplification, if enabled
+    pub generations: usize,                        // Amount of generations ran
+    pub solution_found: bool, // Whether the best individual is within solution_error_threshold
+    pub generation_summaries: Vec<GenerationSummary>, // One summary per generation, starting with the initial population, or after the checkpoint when resumed
+    pub test_errors: Option<Vec<Decimal>>, // Errors of the best individual on the testing data, if provided
+    pub simplified_test_errors: Option<Vec<Decimal>>, // Errors of the simplified individual on the testing data, if provided
+}
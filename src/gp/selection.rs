@@ -5,92 +5,90 @@ use rand::Rng;
 use rand::seq::{IndexedRandom, SliceRandom};
 use rust_decimal::Decimal;
 
-fn tournament_selection(
-    mut pop: Vec<Individual>,
+fn tournament_selection<'a>(
+    pop: &'a [Individual],
     tournament_size: usize,
     direction: SearchDirection,
     rng: &mut impl Rng,
-) -> Individual {
+) -> &'a Individual {
+    let mut pop: Vec<&Individual> = pop.iter().collect();
     pop.shuffle(rng);
-    let mut tournament_set: Vec<Individual> = pop.into_iter().take(tournament_size).collect();
+    let mut tournament_set: Vec<&Individual> = pop.into_iter().take(tournament_size).collect();
     tournament_set.sort_by(|ind0, ind1| ind0.total_fitness.cmp(&ind1.total_fitness));
     match direction {
-        SearchDirection::Min => tournament_set[0].clone(),
-        SearchDirection::Max => tournament_set[tournament_set.len() - 1].clone(),
+        SearchDirection::Min => tournament_set[0],
+        SearchDirection::Max => tournament_set[tournament_set.len() - 1],
     }
 }
 
+/// The error of an evaluated individual on case `t`.
+fn case_error(ind: &Individual, t: usize) -> Decimal {
+    ind.fitness_cases.as_ref().unwrap()[t]
+}
+
 /// Selects individuals based on individual cases rather than an aggregate value.
 /// Use Selection::EpsilonLexicase for regression problems.
 /// Tom Helmuth describing Lexicase: https://youtu.be/Th6Hx3SJOlk
-fn lexicase_selection(
-    mut pop: Vec<Individual>,
+fn lexicase_selection<'a>(
+    pop: &'a [Individual],
     direction: SearchDirection,
     rng: &mut impl Rng,
-) -> Individual {
-    let mut cases: Vec<usize> = (0..pop[0].fitness_cases.clone().unwrap().len()).collect();
+) -> &'a Individual {
+    let mut pop: Vec<&Individual> = pop.iter().collect();
+    let mut cases: Vec<usize> = (0..pop[0].fitness_cases.as_ref().unwrap().len()).collect();
     cases.shuffle(rng);
 
     while pop.len() > 1 && cases.len() > 0 {
         let t = cases[cases.len() - 1];
-        pop.sort_by(|ind0, ind1| {
-            ind0.fitness_cases.clone().unwrap()[t].cmp(&ind1.fitness_cases.clone().unwrap()[t])
-        });
+        pop.sort_by_key(|ind| case_error(ind, t));
         let best: Decimal = match direction {
-            SearchDirection::Min => pop[0].fitness_cases.clone().unwrap()[t],
-            SearchDirection::Max => pop[pop.len() - 1].fitness_cases.clone().unwrap()[t],
+            SearchDirection::Min => case_error(pop[0], t),
+            SearchDirection::Max => case_error(pop[pop.len() - 1], t),
         };
         pop = pop
             .into_iter()
-            .filter(|ind| ind.fitness_cases.clone().unwrap()[t] == best)
+            .filter(|ind| case_error(ind, t) == best)
             .collect();
         cases.pop();
     }
     if pop.len() == 1 {
-        return pop[0].clone();
+        return pop[0];
     } else {
-        return pop.choose(rng).unwrap().clone();
+        return pop.choose(rng).unwrap();
     }
 }
 
 // need to fix this function, best-epsilon needs to be directional.
-fn epsilon_lexicase_selection(
-    mut pop: Vec<Individual>,
+fn epsilon_lexicase_selection<'a>(
+    pop: &'a [Individual],
     direction: SearchDirection,
     rng: &mut impl Rng,
-) -> Individual {
-    let mut cases: Vec<usize> = (0..pop[0].fitness_cases.clone().unwrap().len()).collect();
+) -> &'a Individual {
+    let mut pop: Vec<&Individual> = pop.iter().collect();
+    let mut cases: Vec<usize> = (0..pop[0].fitness_cases.as_ref().unwrap().len()).collect();
     cases.shuffle(rng);
 
     while pop.len() > 1 && cases.len() > 0 {
         let t = cases[cases.len() - 1];
-        pop.sort_by(|ind0, ind1| {
-            ind0.fitness_cases.clone().unwrap()[t].cmp(&ind1.fitness_cases.clone().unwrap()[t])
-        });
+        pop.sort_by_key(|ind| case_error(ind, t));
         let best: Decimal = match direction {
-            SearchDirection::Min => pop[0].fitness_cases.clone().unwrap()[t],
-            SearchDirection::Max => pop[pop.len() - 1].fitness_cases.clone().unwrap()[t],
+            SearchDirection::Min => case_error(pop[0], t),
+            SearchDirection::Max => case_error(pop[pop.len() - 1], t),
         };
         // Contains all values of the specified case from each individual in the population.
         // Tracking which value belongs to who doesn't matter.
-        let pop_cases: Vec<Decimal> = pop
-            .iter()
-            .map(|ind| ind.fitness_cases.clone().unwrap()[t])
-            .collect();
+        let pop_cases: Vec<Decimal> = pop.iter().map(|ind| case_error(ind, t)).collect();
         let epsilon = absolute_median_deviation(&pop_cases);
         pop = pop
             .into_iter()
-            .filter(|ind| {
-                ind.fitness_cases.clone().unwrap()[t] <= best
-                    && ind.fitness_cases.clone().unwrap()[t] >= best - epsilon
-            })
+            .filter(|ind| case_error(ind, t) <= best && case_error(ind, t) >= best - epsilon)
             .collect();
         cases.pop();
     }
     if pop.len() == 1 {
-        return pop[0].clone();
+        return pop[0];
     } else {
-        return pop.choose(rng).unwrap().clone();
+        return pop.choose(rng).unwrap();
     }
 }
 
@@ -101,7 +99,13 @@ pub enum Selection {
     Tournament,
 }
 
-pub fn select_parent(pop: Vec<Individual>, push_args: &PushArgs, rng: &mut impl Rng) -> Individual {
+/// Selects a parent from `pop`. Only borrows the population, so clone the
+/// winner if it needs to be kept.
+pub fn select_parent<'a>(
+    pop: &'a [Individual],
+    push_args: &PushArgs,
+    rng: &mut impl Rng,
+) -> &'a Individual {
     match push_args.parent_selection {
         Selection::Tournament => tournament_selection(
             pop,
@@ -1,10 +1,9 @@
-use super::args::PushArgs;
+use super::args::{ErrorFunction, PushArgs};
 use crate::gp::genome::plushy_to_push;
+use crate::gp::individual::evaluate;
 use crate::push::state::Gene;
-use polars::prelude::*;
 use rand::Rng;
 use rand::prelude::SliceRandom;
-use rand::rng;
 use rust_decimal::Decimal;
 use std::collections::HashSet;
 
@@ -42,10 +41,12 @@ fn delete_k_random<T: Clone>(k: usize, plushy: &[T], rng: &mut impl Rng) -> Vec<
     delete_at_indices(&indices, plushy)
 }
 
-pub fn auto_simplify_plushy<F>(plushy: Vec<Gene>, error_func: F, push_args: &PushArgs) -> Vec<Gene>
-where
-    F: Fn(&PushArgs, &DataFrame, Vec<Gene>) -> Vec<Decimal>,
-{
+pub fn auto_simplify_plushy(
+    plushy: Vec<Gene>,
+    error_func: ErrorFunction,
+    push_args: &PushArgs,
+    rng: &mut impl Rng,
+) -> Vec<Gene> {
     if push_args.simplification_verbose {
         println!(
             "{{ start_plushy_length: {}, k: {} }}",
@@ -59,17 +60,22 @@ where
         .clone()
         .expect("Must provide training_data");
 
-    let mut curr_errors = error_func(&push_args, &training_data, plushy_to_push(plushy.clone()));
+    let mut curr_errors = evaluate(
+        error_func,
+        push_args,
+        &training_data,
+        plushy_to_push(plushy.clone()),
+    );
     let mut step = 0;
     let mut curr_plushy = plushy;
 
     while step < push_args.simplification_steps {
-        let mut rng = rng();
         let random_k = rng.random_range(1..=push_args.simplification_k);
 
-        let new_plushy = delete_k_random(random_k, &curr_plushy, &mut rng);
-        let new_plushy_errors = error_func(
-            &push_args,
+        let new_plushy = delete_k_random(random_k, &curr_plushy, rng);
+        let new_plushy_errors = evaluate(
+            error_func,
+            push_args,
             &training_data,
             plushy_to_push(new_plushy.clone()),
         );
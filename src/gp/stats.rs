@@ -0,0 +1,181 @@
+//! # Population Statistics
+//!
+//! Metrics computed over a whole population, used to see how a run is going
+//! beyond its best individual.
+
+use crate::gp::args::SearchDirection;
+use crate::gp::individual::Individual;
+use crate::gp::utils::median;
+use crate::push::state::Gene;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// The min, max, mean, and median of a set of values. Serialized with the
+/// values as numbers.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Distribution {
+    #[serde(with = "rust_decimal::serde::float")]
+    pub min: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub max: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub mean: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub median: Decimal,
+}
+
+impl Distribution {
+    /// Summarizes a non empty set of values.
+    pub fn from_values(values: Vec<Decimal>) -> Self {
+        Self {
+            min: *values.iter().min().unwrap(),
+            max: *values.iter().max().unwrap(),
+            mean: values.iter().sum::<Decimal>() / Decimal::from(values.len()),
+            median: median(&values),
+        }
+    }
+}
+
+/// Statistics of a single population.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PopulationStats {
+    pub total_fitness: Distribution,     // Total error of the individuals
+    pub plushy_size: Distribution,       // Amount of genes in the plushies
+    pub push_program_size: Distribution, // rec_len of the push programs
+    #[serde(with = "rust_decimal::serde::float")]
+    pub behavioral_diversity: Decimal, // Fraction of distinct error vectors
+    #[serde(with = "rust_decimal::serde::float")]
+    pub genotypic_diversity: Decimal, // Fraction of distinct plushies
+    pub lexicase_elite_counts: Vec<usize>, // Per case, amount of individuals with the best error on it
+}
+
+impl PopulationStats {
+    /// Computes the statistics of a non empty, evaluated population.
+    pub fn from_pop(pop: &[Individual], direction: SearchDirection) -> Self {
+        let pop_size = Decimal::from(pop.len());
+        let distinct_errors: HashSet<&Option<Vec<Decimal>>> =
+            pop.iter().map(|ind| &ind.fitness_cases).collect();
+        let distinct_plushies: HashSet<&Vec<Gene>> = pop.iter().map(|ind| &ind.plushy).collect();
+
+        Self {
+            total_fitness: Distribution::from_values(
+                pop.iter().map(|ind| ind.total_fitness.unwrap()).collect(),
+            ),
+            plushy_size: Distribution::from_values(
+                pop.iter()
+                    .map(|ind| Decimal::from(ind.plushy.len()))
+                    .collect(),
+            ),
+            push_program_size: Distribution::from_values(
+                pop.iter()
+                    .map(|ind| Decimal::from(push_program_size(ind)))
+                    .collect(),
+            ),
+            behavioral_diversity: Decimal::from(distinct_errors.len()) / pop_size,
+            genotypic_diversity: Decimal::from(distinct_plushies.len()) / pop_size,
+            lexicase_elite_counts: lexicase_elite_counts(pop, direction),
+        }
+    }
+}
+
+/// The size of an individual's push program, counting the genes in nested blocks.
+fn push_program_size(ind: &Individual) -> usize {
+    ind.push_program
+        .as_ref()
+        .map_or(0, |program| Gene::Block(program.clone()).rec_len())
+}
+
+/// Counts, for each case, how many individuals have the best error on that
+/// case. Lower counts mean lexicase selection has fewer choices on that case.
+fn lexicase_elite_counts(pop: &[Individual], direction: SearchDirection) -> Vec<usize> {
+    let errors: Vec<&Vec<Decimal>> = pop
+        .iter()
+        .map(|ind| ind.fitness_cases.as_ref().unwrap())
+        .collect();
+    let case_count = errors[0].len();
+    (0..case_count)
+        .map(|case| {
+            let case_errors = errors.iter().map(|errs| errs[case]);
+            let best = match direction {
+                SearchDirection::Min => case_errors.min().unwrap(),
+                SearchDirection::Max => case_errors.max().unwrap(),
+            };
+            errors.iter().filter(|errs| errs[case] == best).count()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gp::genome::plushy_to_push;
+    use crate::instructions::numeric::*;
+    use rust_decimal::dec;
+
+    fn ind(plushy: Vec<Gene>, errors: Vec<Decimal>) -> Individual {
+        Individual {
+            push_program: Some(plushy_to_push(plushy.clone())),
+            plushy,
+            total_fitness: Some(errors.iter().sum()),
+            fitness_cases: Some(errors),
+        }
+    }
+
+    #[test]
+    fn population_stats_test() {
+        let pop = vec![
+            ind(
+                vec![Gene::StateFunc(instruction!(int_add)), Gene::GeneInt(1)],
+                vec![dec!(0.0), dec!(3.0), dec!(1.0)],
+            ),
+            ind(
+                vec![Gene::StateFunc(instruction!(int_add)), Gene::GeneInt(1)],
+                vec![dec!(0.0), dec!(3.0), dec!(1.0)],
+            ),
+            ind(
+                vec![Gene::StateFunc(instruction!(int_sub))],
+                vec![dec!(2.0), dec!(1.0), dec!(1.0)],
+            ),
+            ind(
+                vec![Gene::GeneInt(1), Gene::GeneInt(2), Gene::GeneInt(3)],
+                vec![dec!(1.0), dec!(1.0), dec!(6.0)],
+            ),
+        ];
+
+        let stats = PopulationStats::from_pop(&pop, SearchDirection::Min);
+        assert_eq!(
+            Distribution {
+                min: dec!(4.0),
+                max: dec!(8.0),
+                mean: dec!(5.0),
+                median: dec!(4.0),
+            },
+            stats.total_fitness
+        );
+        assert_eq!(dec!(1), stats.plushy_size.min);
+        assert_eq!(dec!(3), stats.plushy_size.max);
+        assert_eq!(dec!(2), stats.plushy_size.median);
+        assert_eq!(dec!(2), stats.push_program_size.mean);
+        assert_eq!(dec!(0.75), stats.behavioral_diversity);
+        assert_eq!(dec!(0.75), stats.genotypic_diversity);
+        assert_eq!(vec![2, 2, 3], stats.lexicase_elite_counts);
+
+        let stats = PopulationStats::from_pop(&pop, SearchDirection::Max);
+        assert_eq!(vec![1, 2, 1], stats.lexicase_elite_counts);
+    }
+
+    #[test]
+    fn push_program_size_test() {
+        let with_block = Individual {
+            plushy: vec![],
+            push_program: Some(vec![
+                Gene::GeneInt(1),
+                Gene::Block(vec![Gene::GeneInt(2), Gene::Block(vec![])]),
+            ]),
+            total_fitness: None,
+            fitness_cases: None,
+        };
+        assert_eq!(4, push_program_size(&with_block));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::gp::args::ClosingType;
 use crate::gp::genome::OPEN_MAP;
+use crate::push::erc::instantiate;
 use crate::push::state::Gene;
 use polars::prelude::*;
 use rand::Rng;
@@ -162,24 +163,25 @@ pub fn polars_to_gene(polars_value: &AnyValue) -> Gene {
     }
 }
 
+/// Picks a random gene from `instructions` following `closing_type`. An ERC
+/// gene is replaced with a fresh literal.
 pub fn random_instruction(
-    instructions: Vec<Gene>,
+    instructions: &[Gene],
     closing_type: ClosingType,
     rng: &mut impl Rng,
 ) -> Gene {
     match closing_type {
-        ClosingType::Specified => return instructions.choose(rng).unwrap().clone(),
+        ClosingType::Specified => instantiate(instructions.choose(rng).unwrap().clone(), rng),
         ClosingType::Balanced => {
-            let source: Vec<Gene> = instructions
+            let source: Vec<&Gene> = instructions
                 .iter()
                 .filter(|instr| !matches!(instr, Gene::Close))
-                .cloned()
                 .collect();
             let total_opens: usize = source
                 .iter()
                 .filter_map(|instr| {
                     if let Gene::StateFunc(_) = instr {
-                        OPEN_MAP.get(instr).copied().map(|val| val as usize)
+                        OPEN_MAP.get(*instr).copied().map(|val| val as usize)
                     } else {
                         None
                     }
@@ -194,12 +196,12 @@ pub fn random_instruction(
             if rng.random::<f64>() < p {
                 Gene::Close
             } else {
-                source.choose(rng).unwrap().clone()
+                instantiate((*source.choose(rng).unwrap()).clone(), rng)
             }
         }
         ClosingType::None => {
             // Find multi-block instructions (those with opens > 1)
-            let multi_block_instructions: Vec<Gene> = instructions
+            let multi_block_instructions: Vec<&Gene> = instructions
                 .iter()
                 .filter(|instr| {
                     if let Gene::StateFunc(_) = instr {
@@ -208,18 +210,17 @@ pub fn random_instruction(
                         false
                     }
                 })
-                .cloned()
                 .collect();
 
             // Remove Close and multi-block instructions
-            let source: Vec<Gene> = instructions
-                .into_iter() // Take ownership of instructions
+            let source: Vec<&Gene> = instructions
+                .iter()
                 .filter(|instr| {
                     !matches!(instr, Gene::Close) && !multi_block_instructions.contains(instr)
                 })
                 .collect();
 
-            source.choose(rng).unwrap().clone()
+            instantiate((*source.choose(rng).unwrap()).clone(), rng)
         }
     }
 }
@@ -240,8 +241,9 @@ pub fn gaussian_noise_factor(rng: &mut impl Rng) -> Decimal {
     (dec!(-2.0) * u0.ln()).sqrt().unwrap() * (dec!(2.0) * rust_decimal::Decimal::PI * u1).cos()
 }
 
-fn median(data: &Vec<Decimal>) -> Decimal {
-    let mut sorted = data.clone();
+/// Computes the median of a non empty slice.
+pub fn median(data: &[Decimal]) -> Decimal {
+    let mut sorted = data.to_vec();
     sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
 
     let len = sorted.len();
@@ -281,19 +283,22 @@ mod tests {
         let mut rng = StdRng::seed_from_u64(42);
         let genes = most_genes();
 
-        let rand_instruction = random_instruction(genes.clone(), ClosingType::Specified, &mut rng);
+        let rand_instruction = random_instruction(&genes, ClosingType::Specified, &mut rng);
         assert_eq!(
-            Gene::StateFunc(vector_float_from_last_prim),
+            Gene::StateFunc(instruction!(vector_float_from_last_prim)),
             rand_instruction
         );
 
         let mut rng = StdRng::seed_from_u64(32038);
-        let rand_instruction = random_instruction(genes.clone(), ClosingType::Balanced, &mut rng);
-        assert_eq!(Gene::StateFunc(boolean_rotate), rand_instruction);
+        let rand_instruction = random_instruction(&genes, ClosingType::Balanced, &mut rng);
+        assert_eq!(
+            Gene::StateFunc(instruction!(boolean_rotate)),
+            rand_instruction
+        );
 
         let mut rng = StdRng::seed_from_u64(3203890821);
-        let rand_instruction = random_instruction(genes, ClosingType::None, &mut rng);
-        assert_eq!(Gene::StateFunc(code_insert), rand_instruction);
+        let rand_instruction = random_instruction(&genes, ClosingType::None, &mut rng);
+        assert_eq!(Gene::StateFunc(instruction!(code_insert)), rand_instruction);
     }
 
     #[test]
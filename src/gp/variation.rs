@@ -3,6 +3,7 @@ use crate::gp::individual::Individual;
 use crate::gp::selection::select_parent;
 use crate::gp::utils::gaussian_noise_factor;
 use crate::push::state::Gene;
+use polars::prelude::*;
 use rand::Rng;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
@@ -12,6 +13,10 @@ use std::iter::zip;
 use super::args::ClosingType;
 use super::utils::random_instruction;
 
+/// Decimal places the noise added to float literals is rounded to. Keeps the
+/// scale of a float from growing every time it is perturbed.
+const FLOAT_NOISE_PLACES: u32 = 6;
+
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub enum Variation {
     Crossover,
@@ -22,6 +27,7 @@ pub enum Variation {
     UniformDeletion,
     Reproduction,
     UMAD,
+    ConstantMutation,
 }
 
 fn crossover(plushy0: Vec<Gene>, plushy1: Vec<Gene>, mut rng: impl Rng) -> Vec<Gene> {
@@ -130,7 +136,7 @@ fn alternation(
 
 fn uniform_addition(
     plushy: Vec<Gene>,
-    instructions: Vec<Gene>,
+    instructions: &[Gene],
     umad_rate: f64,
     closing_type: ClosingType,
     rng: &mut impl Rng,
@@ -139,7 +145,7 @@ fn uniform_addition(
 
     for gene in plushy {
         if rng.random::<f64>() < umad_rate {
-            let new_instruction = random_instruction(instructions.clone(), closing_type, rng);
+            let new_instruction = random_instruction(instructions, closing_type, rng);
 
             // Randomly decide order (original first or new first)
             if rng.random::<bool>() {
@@ -159,7 +165,7 @@ fn uniform_addition(
 
 fn uniform_replacement(
     plushy: Vec<Gene>,
-    instructions: Vec<Gene>,
+    instructions: &[Gene],
     replacement_rate: f64,
     closing_type: ClosingType,
     rng: &mut impl Rng,
@@ -169,7 +175,7 @@ fn uniform_replacement(
         .map(|gene| {
             if rng.random::<f64>() < replacement_rate {
                 // Replace with random instruction
-                random_instruction(instructions.to_vec(), closing_type, rng)
+                random_instruction(instructions, closing_type, rng)
             } else {
                 // Keep original gene
                 gene
@@ -194,6 +200,56 @@ fn uniform_deletion(plushy: Vec<Gene>, umad_rate: f64, rng: &mut impl Rng) -> Ve
         .collect()
 }
 
+/// Gaussian noise with a standard deviation of `stdev` with probability
+/// `rate`, zero otherwise.
+fn constant_noise(rate: f64, stdev: Decimal, rng: &mut impl Rng) -> Decimal {
+    if rng.random::<f64>() < rate {
+        stdev * gaussian_noise_factor(rng)
+    } else {
+        Decimal::ZERO
+    }
+}
+
+fn perturb_int(val: i128, rate: f64, stdev: Decimal, rng: &mut impl Rng) -> i128 {
+    let noise = constant_noise(rate, stdev, rng).round();
+    val.saturating_add(noise.to_i128().unwrap_or_default())
+}
+
+fn perturb_float(val: Decimal, rate: f64, stdev: Decimal, rng: &mut impl Rng) -> Decimal {
+    let noise = constant_noise(rate, stdev, rng).round_dp(FLOAT_NOISE_PLACES);
+    val.saturating_add(noise)
+}
+
+/// Adds gaussian noise with a standard deviation of `stdev` to numeric
+/// literals. Each int, float, and element of an int or float vector is
+/// perturbed with probability `rate`. Ints get the noise rounded to the
+/// nearest int, floats to `FLOAT_NOISE_PLACES` places.
+fn constant_mutation(
+    plushy: Vec<Gene>,
+    rate: f64,
+    stdev: Decimal,
+    rng: &mut impl Rng,
+) -> Vec<Gene> {
+    plushy
+        .into_iter()
+        .map(|gene| match gene {
+            Gene::GeneInt(val) => Gene::GeneInt(perturb_int(val, rate, stdev, rng)),
+            Gene::GeneFloat(val) => Gene::GeneFloat(perturb_float(val, rate, stdev, rng)),
+            Gene::GeneVectorInt(vals) => Gene::GeneVectorInt(
+                vals.into_iter()
+                    .map(|val| perturb_int(val, rate, stdev, rng))
+                    .collect(),
+            ),
+            Gene::GeneVectorFloat(vals) => Gene::GeneVectorFloat(
+                vals.into_iter()
+                    .map(|val| perturb_float(val, rate, stdev, rng))
+                    .collect(),
+            ),
+            gene => gene,
+        })
+        .collect()
+}
+
 /// Selects a variation operator based on the probabilities
 fn select_variation_op(variation_ops: &HashMap<Variation, f64>, r: f64) -> Variation {
     let mut accum = 0.0;
@@ -209,10 +265,12 @@ fn select_variation_op(variation_ops: &HashMap<Variation, f64>, r: f64) -> Varia
     Variation::Reproduction
 }
 
-/// Creates a new individual based on an argmap variation
+/// Creates a new individual based on an argmap variation. The new individual
+/// is evaluated on `data`.
 pub fn new_individual(
-    pop: Vec<Individual>,
+    pop: &[Individual],
     push_args: &PushArgs,
+    data: &DataFrame,
     rng: &mut impl Rng,
 ) -> Individual {
     // Select variation operator based on probabilities
@@ -221,25 +279,22 @@ pub fn new_individual(
 
     let plushy = match op {
         Variation::Crossover => {
-            let parent1 = select_parent(pop.clone(), push_args, rng);
+            let parent1 = select_parent(pop, push_args, rng);
             let parent2 = select_parent(pop, push_args, rng);
-            crossover(parent1.plushy, parent2.plushy, rng)
+            crossover(parent1.plushy.clone(), parent2.plushy.clone(), rng)
         }
 
         Variation::TailAlignedCrossover => {
-            let parent1 = select_parent(pop.clone(), push_args, rng);
+            let parent1 = select_parent(pop, push_args, rng);
             let parent2 = select_parent(pop, push_args, rng);
-            tail_aligned_crossover(parent1.plushy, parent2.plushy, rng)
+            tail_aligned_crossover(parent1.plushy.clone(), parent2.plushy.clone(), rng)
         }
 
         Variation::UniformAddition => {
             let parent = select_parent(pop, push_args, rng);
             uniform_addition(
                 parent.plushy.clone(),
-                push_args
-                    .instructions
-                    .clone()
-                    .expect("Must provide instructions"),
+                &push_args.gene_pool(),
                 push_args.umad_rate,
                 push_args.closes,
                 rng,
@@ -250,10 +305,7 @@ pub fn new_individual(
             let parent = select_parent(pop, push_args, rng);
             uniform_replacement(
                 parent.plushy.clone(),
-                push_args
-                    .instructions
-                    .clone()
-                    .expect("Must provide instructions!"),
+                &push_args.gene_pool(),
                 push_args.replacement_rate,
                 push_args.closes,
                 rng,
@@ -266,11 +318,11 @@ pub fn new_individual(
         }
 
         Variation::Alternation => {
-            let parent1 = select_parent(pop.clone(), push_args, rng);
+            let parent1 = select_parent(pop, push_args, rng);
             let parent2 = select_parent(pop, push_args, rng);
             alternation(
-                parent1.plushy,
-                parent2.plushy,
+                parent1.plushy.clone(),
+                parent2.plushy.clone(),
                 push_args.alternation_rate,
                 push_args.alignment_deviation,
                 rng,
@@ -284,10 +336,7 @@ pub fn new_individual(
             // Apply uniform addition followed by uniform deletion
             let after_addition = uniform_addition(
                 parent_plushy,
-                push_args
-                    .instructions
-                    .clone()
-                    .expect("Must provide instructions"),
+                &push_args.gene_pool(),
                 push_args.umad_rate,
                 push_args.closes,
                 rng,
@@ -300,14 +349,19 @@ pub fn new_individual(
             let parent = select_parent(pop, push_args, rng);
             parent.plushy.clone()
         }
+
+        Variation::ConstantMutation => {
+            let parent = select_parent(pop, push_args, rng);
+            constant_mutation(
+                parent.plushy.clone(),
+                push_args.constant_mutation_rate,
+                push_args.constant_mutation_stdev,
+                rng,
+            )
+        }
     };
 
-    Individual::with_error(
-        plushy,
-        push_args.error_function.unwrap(),
-        push_args,
-        &push_args.training_data.clone().unwrap(),
-    )
+    Individual::with_error(plushy, push_args.error_function.unwrap(), push_args, data)
 }
 
 #[cfg(test)]
@@ -316,6 +370,7 @@ mod tests {
     use crate::instructions::common::*;
     use crate::instructions::numeric::*;
     use crate::instructions::vector::*;
+    use crate::push::erc::Erc;
     use crate::push::state::Gene;
     use crate::push::utils::most_genes;
     use rand::SeedableRng;
@@ -326,36 +381,36 @@ mod tests {
     fn crossover_test() {
         let rng = StdRng::seed_from_u64(42);
         let plushy0 = vec![
-            Gene::StateFunc(exec_swap),
-            Gene::StateFunc(float_tan),
-            Gene::StateFunc(int_pop),
+            Gene::StateFunc(instruction!(exec_swap)),
+            Gene::StateFunc(instruction!(float_tan)),
+            Gene::StateFunc(instruction!(int_pop)),
             Gene::Close,
-            Gene::StateFunc(exec_flush),
+            Gene::StateFunc(instruction!(exec_flush)),
             Gene::Close,
-            Gene::StateFunc(boolean_pop),
-            Gene::StateFunc(vector_int_swap),
-            Gene::StateFunc(vector_char_pop),
+            Gene::StateFunc(instruction!(boolean_pop)),
+            Gene::StateFunc(instruction!(vector_int_swap)),
+            Gene::StateFunc(instruction!(vector_char_pop)),
         ];
         let plushy1 = vec![
-            Gene::StateFunc(string_swap),
-            Gene::StateFunc(float_arctan),
-            Gene::StateFunc(char_pop),
+            Gene::StateFunc(instruction!(string_swap)),
+            Gene::StateFunc(instruction!(float_arctan)),
+            Gene::StateFunc(instruction!(char_pop)),
             Gene::GeneChar('a'),
-            Gene::StateFunc(code_flush),
+            Gene::StateFunc(instruction!(code_flush)),
             Gene::GeneInt(1),
-            Gene::StateFunc(float_pop),
+            Gene::StateFunc(instruction!(float_pop)),
         ];
         let res_plushy = crossover(plushy0, plushy1, rng);
         assert_eq!(
             vec![
-                Gene::StateFunc(string_swap),
-                Gene::StateFunc(float_tan),
-                Gene::StateFunc(char_pop),
+                Gene::StateFunc(instruction!(string_swap)),
+                Gene::StateFunc(instruction!(float_tan)),
+                Gene::StateFunc(instruction!(char_pop)),
                 Gene::Close,
-                Gene::StateFunc(exec_flush),
+                Gene::StateFunc(instruction!(exec_flush)),
                 Gene::Close,
-                Gene::StateFunc(boolean_pop),
-                Gene::StateFunc(vector_char_pop),
+                Gene::StateFunc(instruction!(boolean_pop)),
+                Gene::StateFunc(instruction!(vector_char_pop)),
             ],
             res_plushy
         )
@@ -365,36 +420,36 @@ mod tests {
     fn tail_aligned_crossover_test() {
         let rng = StdRng::seed_from_u64(42);
         let plushy0 = vec![
-            Gene::StateFunc(exec_swap),
-            Gene::StateFunc(float_tan),
-            Gene::StateFunc(int_pop),
+            Gene::StateFunc(instruction!(exec_swap)),
+            Gene::StateFunc(instruction!(float_tan)),
+            Gene::StateFunc(instruction!(int_pop)),
             Gene::Close,
-            Gene::StateFunc(exec_flush),
+            Gene::StateFunc(instruction!(exec_flush)),
             Gene::Close,
-            Gene::StateFunc(boolean_pop),
-            Gene::StateFunc(vector_int_swap),
-            Gene::StateFunc(vector_char_pop),
+            Gene::StateFunc(instruction!(boolean_pop)),
+            Gene::StateFunc(instruction!(vector_int_swap)),
+            Gene::StateFunc(instruction!(vector_char_pop)),
         ];
         let plushy1 = vec![
-            Gene::StateFunc(string_swap),
-            Gene::StateFunc(float_arctan),
-            Gene::StateFunc(char_pop),
+            Gene::StateFunc(instruction!(string_swap)),
+            Gene::StateFunc(instruction!(float_arctan)),
+            Gene::StateFunc(instruction!(char_pop)),
             Gene::GeneChar('a'),
-            Gene::StateFunc(code_flush),
+            Gene::StateFunc(instruction!(code_flush)),
             Gene::GeneInt(1),
-            Gene::StateFunc(float_pop),
+            Gene::StateFunc(instruction!(float_pop)),
         ];
         let res_plushy = tail_aligned_crossover(plushy0, plushy1, rng);
         assert_eq!(
             vec![
-                Gene::StateFunc(float_tan),
-                Gene::StateFunc(string_swap),
+                Gene::StateFunc(instruction!(float_tan)),
+                Gene::StateFunc(instruction!(string_swap)),
                 Gene::Close,
-                Gene::StateFunc(exec_flush),
+                Gene::StateFunc(instruction!(exec_flush)),
                 Gene::Close,
-                Gene::StateFunc(boolean_pop),
+                Gene::StateFunc(instruction!(boolean_pop)),
                 Gene::GeneInt(1),
-                Gene::StateFunc(vector_char_pop),
+                Gene::StateFunc(instruction!(vector_char_pop)),
             ],
             res_plushy
         )
@@ -404,33 +459,33 @@ mod tests {
     fn alternation_test() {
         let rng = StdRng::seed_from_u64(42);
         let plushy0 = vec![
-            Gene::StateFunc(exec_swap),
-            Gene::StateFunc(float_tan),
-            Gene::StateFunc(int_pop),
+            Gene::StateFunc(instruction!(exec_swap)),
+            Gene::StateFunc(instruction!(float_tan)),
+            Gene::StateFunc(instruction!(int_pop)),
             Gene::Close,
-            Gene::StateFunc(exec_flush),
+            Gene::StateFunc(instruction!(exec_flush)),
             Gene::Close,
-            Gene::StateFunc(boolean_pop),
-            Gene::StateFunc(vector_int_swap),
-            Gene::StateFunc(vector_char_pop),
+            Gene::StateFunc(instruction!(boolean_pop)),
+            Gene::StateFunc(instruction!(vector_int_swap)),
+            Gene::StateFunc(instruction!(vector_char_pop)),
         ];
         let plushy1 = vec![
-            Gene::StateFunc(string_swap),
-            Gene::StateFunc(float_arctan),
-            Gene::StateFunc(char_pop),
+            Gene::StateFunc(instruction!(string_swap)),
+            Gene::StateFunc(instruction!(float_arctan)),
+            Gene::StateFunc(instruction!(char_pop)),
             Gene::GeneChar('a'),
-            Gene::StateFunc(code_flush),
+            Gene::StateFunc(instruction!(code_flush)),
             Gene::GeneInt(1),
-            Gene::StateFunc(float_pop),
+            Gene::StateFunc(instruction!(float_pop)),
         ];
         let res_plushy = alternation(plushy0, plushy1, 50, dec!(2.0), rng);
         assert_eq!(
             vec![
-                Gene::StateFunc(char_pop),
+                Gene::StateFunc(instruction!(char_pop)),
                 Gene::GeneChar('a'),
-                Gene::StateFunc(boolean_pop),
-                Gene::StateFunc(vector_int_swap),
-                Gene::StateFunc(vector_char_pop),
+                Gene::StateFunc(instruction!(boolean_pop)),
+                Gene::StateFunc(instruction!(vector_int_swap)),
+                Gene::StateFunc(instruction!(vector_char_pop)),
             ],
             res_plushy
         );
@@ -440,23 +495,28 @@ mod tests {
     fn uniform_addition_test() {
         let mut rng = StdRng::seed_from_u64(42);
         let plushy0 = vec![
-            Gene::StateFunc(exec_swap),
-            Gene::StateFunc(float_tan),
-            Gene::StateFunc(int_pop),
+            Gene::StateFunc(instruction!(exec_swap)),
+            Gene::StateFunc(instruction!(float_tan)),
+            Gene::StateFunc(instruction!(int_pop)),
             Gene::Close,
         ];
-        let res_plushy =
-            uniform_addition(plushy0, most_genes(), 0.75, ClosingType::Balanced, &mut rng);
+        let res_plushy = uniform_addition(
+            plushy0,
+            &most_genes(),
+            0.75,
+            ClosingType::Balanced,
+            &mut rng,
+        );
         assert_eq!(
             vec![
-                Gene::StateFunc(exec_swap),
-                Gene::StateFunc(float_min),
-                Gene::StateFunc(float_tan),
+                Gene::StateFunc(instruction!(exec_swap)),
+                Gene::StateFunc(instruction!(float_min)),
+                Gene::StateFunc(instruction!(float_tan)),
                 Gene::Close,
-                Gene::StateFunc(int_pop),
-                Gene::StateFunc(int_yank_dup),
+                Gene::StateFunc(instruction!(int_pop)),
+                Gene::StateFunc(instruction!(int_yank_dup)),
                 Gene::Close,
-                Gene::StateFunc(float_is_empty),
+                Gene::StateFunc(instruction!(float_is_empty)),
             ],
             res_plushy
         );
@@ -466,22 +526,22 @@ mod tests {
     fn uniform_replacement_test() {
         let mut rng = StdRng::seed_from_u64(42);
         let plushy0 = vec![
-            Gene::StateFunc(exec_swap),
-            Gene::StateFunc(float_tan),
-            Gene::StateFunc(int_pop),
+            Gene::StateFunc(instruction!(exec_swap)),
+            Gene::StateFunc(instruction!(float_tan)),
+            Gene::StateFunc(instruction!(int_pop)),
             Gene::Close,
             Gene::Close,
             Gene::GeneInt(1),
         ];
         let res_plushy =
-            uniform_replacement(plushy0, most_genes(), 0.5, ClosingType::Balanced, &mut rng);
+            uniform_replacement(plushy0, &most_genes(), 0.5, ClosingType::Balanced, &mut rng);
         assert_eq!(
             vec![
-                Gene::StateFunc(exec_swap),
-                Gene::StateFunc(float_tan),
-                Gene::StateFunc(int_pop),
+                Gene::StateFunc(instruction!(exec_swap)),
+                Gene::StateFunc(instruction!(float_tan)),
+                Gene::StateFunc(instruction!(int_pop)),
                 Gene::Close,
-                Gene::StateFunc(vector_float_sort_reverse),
+                Gene::StateFunc(instruction!(vector_float_sort_reverse)),
                 Gene::GeneInt(1),
             ],
             res_plushy
@@ -492,9 +552,9 @@ mod tests {
     fn uniform_deletion_test() {
         let mut rng = StdRng::seed_from_u64(42);
         let plushy0 = vec![
-            Gene::StateFunc(exec_swap),
-            Gene::StateFunc(float_tan),
-            Gene::StateFunc(int_pop),
+            Gene::StateFunc(instruction!(exec_swap)),
+            Gene::StateFunc(instruction!(float_tan)),
+            Gene::StateFunc(instruction!(int_pop)),
             Gene::Close,
             Gene::Close,
             Gene::GeneInt(1),
@@ -502,13 +562,93 @@ mod tests {
         let res_plushy = uniform_deletion(plushy0, 0.5, &mut rng);
         assert_eq!(
             vec![
-                Gene::StateFunc(exec_swap),
-                Gene::StateFunc(float_tan),
-                Gene::StateFunc(int_pop),
+                Gene::StateFunc(instruction!(exec_swap)),
+                Gene::StateFunc(instruction!(float_tan)),
+                Gene::StateFunc(instruction!(int_pop)),
                 Gene::Close,
                 Gene::GeneInt(1),
             ],
             res_plushy
         );
     }
+    #[test]
+    fn uniform_erc_test() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let genes = vec![Gene::Erc(Erc::Int { min: 0, max: 1000 })];
+        let plushy0 = vec![Gene::StateFunc(instruction!(int_pop)); 20];
+
+        let added = uniform_addition(plushy0.clone(), &genes, 0.5, ClosingType::None, &mut rng);
+        let replaced = uniform_replacement(plushy0, &genes, 0.5, ClosingType::None, &mut rng);
+        for plushy in [added, replaced] {
+            let ints: Vec<&Gene> = plushy
+                .iter()
+                .filter(|gene| matches!(gene, Gene::GeneInt(_)))
+                .collect();
+            assert!(ints.len() > 1);
+            assert!(!plushy.iter().any(|gene| matches!(gene, Gene::Erc(_))));
+            // Every sample is a fresh constant.
+            assert!(ints.iter().any(|gene| *gene != ints[0]));
+        }
+    }
+    #[test]
+    fn constant_mutation_test() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let plushy0 = vec![
+            Gene::GeneInt(10),
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::GeneFloat(dec!(1.5)),
+            Gene::GeneFloat(dec!(0.123456789)),
+            Gene::GeneVectorInt(vec![1, 2, 3]),
+            Gene::GeneVectorFloat(vec![dec!(0.0), dec!(1.0)]),
+            Gene::GeneBoolean(true),
+            Gene::Close,
+        ];
+
+        let res_plushy = constant_mutation(plushy0.clone(), 0.0, dec!(5.0), &mut rng);
+        assert_eq!(plushy0, res_plushy);
+
+        let res_plushy = constant_mutation(plushy0.clone(), 1.0, dec!(5.0), &mut rng);
+        assert_eq!(plushy0.len(), res_plushy.len());
+        for (gene0, gene1) in plushy0.iter().zip(res_plushy.iter()) {
+            match (gene0, gene1) {
+                (Gene::GeneInt(_), Gene::GeneInt(_)) | (Gene::GeneFloat(_), Gene::GeneFloat(_)) => {
+                    assert_ne!(gene0, gene1)
+                }
+                (Gene::GeneVectorInt(vals0), Gene::GeneVectorInt(vals1)) => {
+                    assert_eq!(vals0.len(), vals1.len());
+                    assert_ne!(vals0, vals1);
+                }
+                (Gene::GeneVectorFloat(vals0), Gene::GeneVectorFloat(vals1)) => {
+                    assert_eq!(vals0.len(), vals1.len());
+                    assert_ne!(vals0, vals1);
+                }
+                _ => assert_eq!(gene0, gene1),
+            }
+        }
+
+        // Ints saturate at the bounds rather than overflow.
+        for bound in [i128::MAX, i128::MIN] {
+            let res_plushy =
+                constant_mutation(vec![Gene::GeneInt(bound); 20], 1.0, dec!(5.0), &mut rng);
+            let vals: Vec<i128> = res_plushy
+                .iter()
+                .map(|gene| match gene {
+                    Gene::GeneInt(val) => *val,
+                    _ => panic!("Expected an int, found {:?}", gene),
+                })
+                .collect();
+            assert!(vals.contains(&bound));
+            assert!(vals.iter().all(|val| val.abs_diff(bound) <= 50));
+        }
+
+        // The scale of a float stays bounded however often it is perturbed.
+        let mut plushy = vec![Gene::GeneFloat(dec!(1.5))];
+        for _ in 0..100 {
+            plushy = constant_mutation(plushy, 1.0, dec!(0.3), &mut rng);
+        }
+        match &plushy[0] {
+            Gene::GeneFloat(val) => assert!(val.scale() <= FLOAT_NOISE_PLACES),
+            gene => panic!("Expected a float, found {:?}", gene),
+        }
+    }
 }
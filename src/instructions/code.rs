@@ -114,9 +114,10 @@ pub fn code_do_then_pop(state: &mut PushState) {
         return;
     }
     let c = state.code[state.code.len() - 1].clone();
-    state.exec.push(Gene::StateFunc(code_pop));
+    state.exec.push(Gene::StateFunc(instruction!(code_pop)));
     state.exec.push(c);
 }
+register_instruction!(code_do_then_pop, [code], [exec], 0);
 
 /// Evaluates the top item on the code stack based off
 /// the range of two ints from the int stack.
@@ -139,14 +140,15 @@ pub fn code_do_range(state: &mut PushState) {
         state.exec.push(Gene::Block(vec![
             Gene::GeneInt(current_idx + increment),
             Gene::GeneInt(dest_idx),
-            Gene::StateFunc(code_from_exec),
+            Gene::StateFunc(instruction!(code_from_exec)),
             to_do.clone(),
-            Gene::StateFunc(code_do_range),
+            Gene::StateFunc(instruction!(code_do_range)),
         ]));
     }
     state.int.push(current_idx);
     state.exec.push(to_do);
 }
+register_instruction!(code_do_range, [code, int, int], [exec, int], 0);
 
 /// Evaluates the top item on the exec stack based off
 /// the range of two ints from the int stack.
@@ -169,13 +171,14 @@ pub fn exec_do_range(state: &mut PushState) {
         state.exec.push(Gene::Block(vec![
             Gene::GeneInt(current_idx + increment),
             Gene::GeneInt(dest_idx),
-            Gene::StateFunc(exec_do_range),
+            Gene::StateFunc(instruction!(exec_do_range)),
             to_do.clone(),
         ]));
     }
     state.int.push(current_idx);
     state.exec.push(to_do);
 }
+register_instruction!(exec_do_range, [exec, int, int], [exec, int], 1);
 
 /// Evaluates the top item on the code stack n times. N pulled from
 /// top of int stack.
@@ -191,11 +194,12 @@ pub fn code_do_count(state: &mut PushState) {
     state.exec.push(Gene::Block(vec![
         Gene::GeneInt(0),
         Gene::GeneInt(count - 1),
-        Gene::StateFunc(code_from_exec),
+        Gene::StateFunc(instruction!(code_from_exec)),
         code,
-        Gene::StateFunc(code_do_range),
+        Gene::StateFunc(instruction!(code_do_range)),
     ]));
 }
+register_instruction!(code_do_count, [code, int], [exec], 0);
 
 /// Evaluates the top item on the exec stack n times. N pulled from top
 /// of int stack.
@@ -211,10 +215,11 @@ pub fn exec_do_count(state: &mut PushState) {
     state.exec.push(Gene::Block(vec![
         Gene::GeneInt(0),
         Gene::GeneInt(count - 1),
-        Gene::StateFunc(exec_do_range),
+        Gene::StateFunc(instruction!(exec_do_range)),
         code,
     ]));
 }
+register_instruction!(exec_do_count, [exec, int], [exec], 1);
 
 /// Evaluates the top item on the code stack n times but differently that
 /// than `code_do_count`. Don't ask, it uses a block for some reason.
@@ -227,15 +232,16 @@ pub fn code_do_times(state: &mut PushState) {
     }
     let code = state.code.pop().unwrap();
     let times = state.int.pop().unwrap();
-    let nested_block = Gene::Block(vec![Gene::StateFunc(int_pop), code]);
+    let nested_block = Gene::Block(vec![Gene::StateFunc(instruction!(int_pop)), code]);
     state.exec.push(Gene::Block(vec![
         Gene::GeneInt(0),
         Gene::GeneInt(times - 1),
-        Gene::StateFunc(code_from_exec),
+        Gene::StateFunc(instruction!(code_from_exec)),
         nested_block,
-        Gene::StateFunc(code_do_range),
+        Gene::StateFunc(instruction!(code_do_range)),
     ]));
 }
+register_instruction!(code_do_times, [code, int], [exec], 0);
 
 /// Evaluates the top item on the code stack n times, also different :shrug:
 pub fn exec_do_times(state: &mut PushState) {
@@ -247,14 +253,15 @@ pub fn exec_do_times(state: &mut PushState) {
     }
     let code = state.exec.pop().unwrap();
     let times = state.int.pop().unwrap();
-    let nested_block = Gene::Block(vec![Gene::StateFunc(int_pop), code]);
+    let nested_block = Gene::Block(vec![Gene::StateFunc(instruction!(int_pop)), code]);
     state.exec.push(Gene::Block(vec![
         Gene::GeneInt(0),
         Gene::GeneInt(times - 1),
-        Gene::StateFunc(exec_do_range),
+        Gene::StateFunc(instruction!(exec_do_range)),
         nested_block,
     ]));
 }
+register_instruction!(exec_do_times, [exec, int], [exec], 1);
 
 /// Evaluates the top item on the exec stack until the top bool isn't true
 pub fn exec_while(state: &mut PushState) {
@@ -267,12 +274,13 @@ pub fn exec_while(state: &mut PushState) {
     }
     let code = state.exec[state.exec.len() - 1].clone();
     if state.boolean.pop().unwrap() {
-        state.exec.push(Gene::StateFunc(exec_while));
+        state.exec.push(Gene::StateFunc(instruction!(exec_while)));
         state.exec.push(code);
     } else {
         state.exec.pop().unwrap();
     }
 }
+register_instruction!(exec_while, [exec, boolean], [exec], 1);
 
 /// Evaluates the top item on the exec stack at least once until the top bool
 /// isn't true
@@ -281,9 +289,61 @@ pub fn exec_do_while(state: &mut PushState) {
         return;
     }
     let code = state.exec[state.exec.len() - 1].clone();
-    state.exec.push(Gene::StateFunc(exec_while));
+    state.exec.push(Gene::StateFunc(instruction!(exec_while)));
     state.exec.push(code);
 }
+register_instruction!(exec_do_while, [exec], [exec], 1);
+
+/// The K combinator. Removes the second item on the exec stack, keeping the top.
+pub fn exec_k(state: &mut PushState) {
+    if state.exec.len() < 2 {
+        return;
+    }
+    let a = state.exec.pop().unwrap();
+    state.exec.pop();
+    state.exec.push(a);
+}
+register_instruction!(exec_k, [exec, exec], [exec], 2);
+
+/// The S combinator. Pops A, B, and C from the exec stack and pushes
+/// `(B C)`, then C, then A. A noop when `(B C)` would have more than
+/// `max_points` points.
+pub fn exec_s(state: &mut PushState) {
+    if state.exec.len() < 3 {
+        return;
+    }
+    let len = state.exec.len();
+    let bc = Gene::Block(vec![
+        state.exec[len - 2].clone(),
+        state.exec[len - 3].clone(),
+    ]);
+    if bc.points() > state.max_points {
+        return;
+    }
+    let a = state.exec.pop().unwrap();
+    state.exec.pop();
+    let c = state.exec.pop().unwrap();
+    state.exec.push(bc);
+    state.exec.push(c);
+    state.exec.push(a);
+}
+register_instruction!(exec_s, [exec, exec, exec], [exec], 3);
+
+/// The Y combinator. Puts `(exec_y A)` under the top exec item A, so A is
+/// evaluated over and over. Only the step limit of `interpret_program`
+/// stops the recursion, the exec stack itself doesn't grow from it.
+pub fn exec_y(state: &mut PushState) {
+    if state.exec.is_empty() {
+        return;
+    }
+    let a = state.exec[state.exec.len() - 1].clone();
+    let idx = state.exec.len() - 1;
+    state.exec.insert(
+        idx,
+        Gene::Block(vec![Gene::StateFunc(instruction!(exec_y)), a]),
+    );
+}
+register_instruction!(exec_y, [exec], [exec], 1);
 
 /// Evaluates the top exec item for each element of the top block on the code stack.
 /// If top item isn't a block, wrapped in one.
@@ -301,18 +361,23 @@ pub fn code_map(state: &mut PushState) {
     let mut contents = Vec::new();
 
     for item in c_vec.clone().into_iter() {
-        let code_block = vec![Gene::StateFunc(code_from_exec), item, e.clone()];
+        let code_block = vec![
+            Gene::StateFunc(instruction!(code_from_exec)),
+            item,
+            e.clone(),
+        ];
         contents.push(Gene::Block(code_block));
     }
 
-    contents.push(Gene::StateFunc(code_wrap_block));
+    contents.push(Gene::StateFunc(instruction!(code_wrap_block)));
 
     for _ in c_vec.into_iter().skip(1) {
-        contents.push(Gene::StateFunc(code_combine));
+        contents.push(Gene::StateFunc(instruction!(code_combine)));
     }
 
     state.exec.push(Gene::Block(contents));
 }
+register_instruction!(code_map, [exec, code], [exec], 1);
 
 /// If top bool is true, execute top element of code/exec stack and skip the second.
 /// If false, execute second element and skip the top.
@@ -330,6 +395,7 @@ pub fn code_when(state: &mut PushState) {
         state.exec.push(code);
     }
 }
+register_instruction!(code_when, [code, boolean], [exec], 0);
 
 /// Pops the next item on the exec stack without evaluating it if the top
 /// bool is False, otherwise has no effect.
@@ -341,6 +407,7 @@ pub fn exec_when(state: &mut PushState) {
         state.exec.pop();
     }
 }
+register_instruction!(exec_when, [exec, boolean], [], 1);
 
 /// Pushes true if the second code item is found within the first item.
 /// If the first item isn't a block, coerced into one.
@@ -467,31 +534,32 @@ macro_rules! noop {
             pub fn [< $stack $name >] (_state: &mut PushState) {
                 ()
             }
+            register_instruction!([< $stack $name >], [], [], 0);
         }
     };
 }
 
 macro_rules! make_code_instructions {
     ($stack:ident) => {
-        make_instruction_new!(_is_block, $stack, boolean, $stack);
-        make_instruction_new!(_is_singular, $stack, boolean, $stack);
-        make_instruction_new!(_length, $stack, int, $stack);
-        make_instruction_new!(_first, $stack, $stack, $stack);
-        make_instruction_new!(_last, $stack, $stack, $stack);
-        make_instruction_new!(_rest, $stack, $stack, $stack);
-        make_instruction_new!(_but_last, $stack, $stack, $stack);
-        make_instruction_new!(_wrap_block, $stack, $stack, $stack);
-        make_instruction_new!(_combine, $stack, $stack, $stack, $stack);
-        make_instruction_new!(_if, $stack, exec, $stack, $stack, boolean);
-        make_instruction_new!(_member, $stack, boolean, $stack, $stack);
-        make_instruction_new!(_nth, $stack, $stack, $stack, int);
+        make_instruction_new!(_is_block, $stack, boolean, $stack; exec_blocks!($stack, 1));
+        make_instruction_new!(_is_singular, $stack, boolean, $stack; exec_blocks!($stack, 1));
+        make_instruction_new!(_length, $stack, int, $stack; exec_blocks!($stack, 1));
+        make_instruction_new!(_first, $stack, $stack, $stack; exec_blocks!($stack, 1));
+        make_instruction_new!(_last, $stack, $stack, $stack; exec_blocks!($stack, 1));
+        make_instruction_new!(_rest, $stack, $stack, $stack; exec_blocks!($stack, 1));
+        make_instruction_new!(_but_last, $stack, $stack, $stack; exec_blocks!($stack, 1));
+        make_instruction_points!(_wrap_block, $stack, $stack, $stack; exec_blocks!($stack, 1));
+        make_instruction_points!(_combine, $stack, $stack, $stack, $stack; exec_blocks!($stack, 2));
+        make_instruction_new!(_if, $stack, exec, $stack, $stack, boolean; exec_blocks!($stack, 2));
+        make_instruction_new!(_member, $stack, boolean, $stack, $stack; exec_blocks!($stack, 2));
+        make_instruction_new!(_nth, $stack, $stack, $stack, int; exec_blocks!($stack, 1));
         make_instruction_empty!(_make_empty_block, $stack, $stack, Gene);
-        make_instruction_new!(_is_empty_block, $stack, boolean, $stack);
-        make_instruction_new!(_size, $stack, int, $stack);
-        make_instruction_new!(_extract, $stack, $stack, $stack, int);
-        make_instruction_new!(_insert, $stack, $stack, $stack, $stack, int);
-        make_instruction_new!(_first_position, $stack, int, $stack, $stack);
-        make_instruction_new!(_reverse, $stack, $stack, $stack);
+        make_instruction_new!(_is_empty_block, $stack, boolean, $stack; exec_blocks!($stack, 1));
+        make_instruction_new!(_size, $stack, int, $stack; exec_blocks!($stack, 1));
+        make_instruction_new!(_extract, $stack, $stack, $stack, int; exec_blocks!($stack, 1));
+        make_instruction_points!(_insert, $stack, $stack, $stack, $stack, int; exec_blocks!($stack, 2));
+        make_instruction_new!(_first_position, $stack, int, $stack, $stack; exec_blocks!($stack, 2));
+        make_instruction_new!(_reverse, $stack, $stack, $stack; exec_blocks!($stack, 1));
     };
 }
 
@@ -513,13 +581,16 @@ all_code_instructions!();
 mod tests {
     use super::*;
     use crate::{
-        instructions::numeric::int_add,
+        instructions::numeric::{int_add, int_inc},
+        instructions::utils::Overflow,
         push::{interpreter::interpret_program, state::EMPTY_STATE},
     };
     use rust_decimal::dec;
 
     const STEP_LIMIT: usize = 1000;
     const MAX_STACK_SIZE: usize = 1000;
+    const MAX_OUTPUT_LENGTH: usize = 1000;
+    const MAX_POINTS: usize = 100;
 
     #[test]
     fn is_block_test() {
@@ -742,11 +813,17 @@ mod tests {
     fn code_do_then_pop_test() {
         let mut test_state = EMPTY_STATE;
 
-        test_state.code.push(Gene::StateFunc(int_add));
+        test_state.code.push(Gene::StateFunc(instruction!(int_add)));
         code_do_then_pop(&mut test_state);
-        assert_eq!(vec![Gene::StateFunc(int_add)], test_state.code);
         assert_eq!(
-            vec![Gene::StateFunc(code_pop), Gene::StateFunc(int_add)],
+            vec![Gene::StateFunc(instruction!(int_add))],
+            test_state.code
+        );
+        assert_eq!(
+            vec![
+                Gene::StateFunc(instruction!(code_pop)),
+                Gene::StateFunc(instruction!(int_add))
+            ],
             test_state.exec
         );
     }
@@ -756,13 +833,21 @@ mod tests {
         let mut test_state = EMPTY_STATE;
 
         test_state.exec = vec![
-            Gene::StateFunc(code_do_range),
-            Gene::StateFunc(int_add),
-            Gene::StateFunc(code_from_exec),
+            Gene::StateFunc(instruction!(code_do_range)),
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::StateFunc(instruction!(code_from_exec)),
             Gene::GeneInt(6),
             Gene::GeneInt(3),
         ];
-        interpret_program(&mut test_state, STEP_LIMIT, MAX_STACK_SIZE);
+        interpret_program(
+            &mut test_state,
+            STEP_LIMIT,
+            MAX_STACK_SIZE,
+            MAX_OUTPUT_LENGTH,
+            MAX_POINTS,
+            Overflow::Checked,
+        )
+        .unwrap();
         assert_eq!(vec![18], test_state.int);
     }
 
@@ -771,13 +856,21 @@ mod tests {
         let mut test_state = EMPTY_STATE;
 
         test_state.exec = vec![
-            Gene::StateFunc(int_add),
-            Gene::StateFunc(exec_do_range),
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::StateFunc(instruction!(exec_do_range)),
             Gene::GeneInt(5),
             Gene::GeneInt(3),
             Gene::GeneInt(8),
         ];
-        interpret_program(&mut test_state, STEP_LIMIT, MAX_STACK_SIZE);
+        interpret_program(
+            &mut test_state,
+            STEP_LIMIT,
+            MAX_STACK_SIZE,
+            MAX_OUTPUT_LENGTH,
+            MAX_POINTS,
+            Overflow::Checked,
+        )
+        .unwrap();
         assert_eq!(vec![20], test_state.int);
     }
 
@@ -786,12 +879,20 @@ mod tests {
         let mut test_state = EMPTY_STATE;
 
         test_state.exec = vec![
-            Gene::StateFunc(code_do_count),
-            Gene::StateFunc(int_add),
-            Gene::StateFunc(code_from_exec),
+            Gene::StateFunc(instruction!(code_do_count)),
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::StateFunc(instruction!(code_from_exec)),
             Gene::GeneInt(6),
         ];
-        interpret_program(&mut test_state, STEP_LIMIT, MAX_STACK_SIZE);
+        interpret_program(
+            &mut test_state,
+            STEP_LIMIT,
+            MAX_STACK_SIZE,
+            MAX_OUTPUT_LENGTH,
+            MAX_POINTS,
+            Overflow::Checked,
+        )
+        .unwrap();
         assert_eq!(vec![15], test_state.int);
     }
 
@@ -800,12 +901,20 @@ mod tests {
         let mut test_state = EMPTY_STATE;
 
         test_state.exec = vec![
-            Gene::StateFunc(int_add),
-            Gene::StateFunc(exec_do_count),
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::StateFunc(instruction!(exec_do_count)),
             Gene::GeneInt(5),
             Gene::GeneInt(3),
         ];
-        interpret_program(&mut test_state, STEP_LIMIT, MAX_STACK_SIZE);
+        interpret_program(
+            &mut test_state,
+            STEP_LIMIT,
+            MAX_STACK_SIZE,
+            MAX_OUTPUT_LENGTH,
+            MAX_POINTS,
+            Overflow::Checked,
+        )
+        .unwrap();
         assert_eq!(vec![13], test_state.int);
     }
 
@@ -814,15 +923,23 @@ mod tests {
         let mut test_state = EMPTY_STATE;
 
         test_state.exec = vec![
-            Gene::StateFunc(code_do_times),
-            Gene::StateFunc(int_add),
-            Gene::StateFunc(code_from_exec),
+            Gene::StateFunc(instruction!(code_do_times)),
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::StateFunc(instruction!(code_from_exec)),
             Gene::GeneInt(2),
             Gene::GeneInt(4),
             Gene::GeneInt(3),
             Gene::GeneInt(6),
         ];
-        interpret_program(&mut test_state, STEP_LIMIT, MAX_STACK_SIZE);
+        interpret_program(
+            &mut test_state,
+            STEP_LIMIT,
+            MAX_STACK_SIZE,
+            MAX_OUTPUT_LENGTH,
+            MAX_POINTS,
+            Overflow::Checked,
+        )
+        .unwrap();
         assert_eq!(vec![13], test_state.int);
     }
 
@@ -831,14 +948,22 @@ mod tests {
         let mut test_state = EMPTY_STATE;
 
         test_state.exec = vec![
-            Gene::StateFunc(int_add),
-            Gene::StateFunc(exec_do_times),
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::StateFunc(instruction!(exec_do_times)),
             Gene::GeneInt(7),
             Gene::GeneInt(4),
             Gene::GeneInt(5),
             Gene::GeneInt(3),
         ];
-        interpret_program(&mut test_state, STEP_LIMIT, MAX_STACK_SIZE);
+        interpret_program(
+            &mut test_state,
+            STEP_LIMIT,
+            MAX_STACK_SIZE,
+            MAX_OUTPUT_LENGTH,
+            MAX_POINTS,
+            Overflow::Checked,
+        )
+        .unwrap();
         assert_eq!(vec![12], test_state.int);
     }
 
@@ -848,8 +973,19 @@ mod tests {
 
         test_state.boolean = vec![false, true, false, true, true, true];
         test_state.int = vec![1, 1, 1, 1];
-        test_state.exec = vec![Gene::StateFunc(int_add), Gene::StateFunc(exec_while)];
-        interpret_program(&mut test_state, STEP_LIMIT, MAX_STACK_SIZE);
+        test_state.exec = vec![
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::StateFunc(instruction!(exec_while)),
+        ];
+        interpret_program(
+            &mut test_state,
+            STEP_LIMIT,
+            MAX_STACK_SIZE,
+            MAX_OUTPUT_LENGTH,
+            MAX_POINTS,
+            Overflow::Checked,
+        )
+        .unwrap();
         assert_eq!(vec![4], test_state.int);
         assert_eq!(vec![false, true], test_state.boolean);
         test_state.int.clear();
@@ -857,8 +993,19 @@ mod tests {
 
         test_state.boolean = vec![false, true, false, true, true, false];
         test_state.int = vec![1, 1, 1, 1];
-        test_state.exec = vec![Gene::StateFunc(int_add), Gene::StateFunc(exec_while)];
-        interpret_program(&mut test_state, STEP_LIMIT, MAX_STACK_SIZE);
+        test_state.exec = vec![
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::StateFunc(instruction!(exec_while)),
+        ];
+        interpret_program(
+            &mut test_state,
+            STEP_LIMIT,
+            MAX_STACK_SIZE,
+            MAX_OUTPUT_LENGTH,
+            MAX_POINTS,
+            Overflow::Checked,
+        )
+        .unwrap();
         assert_eq!(vec![1, 1, 1, 1], test_state.int);
     }
 
@@ -868,11 +1015,143 @@ mod tests {
 
         test_state.boolean = vec![false, true, false, true, true, false];
         test_state.int = vec![1, 1, 1, 1];
-        test_state.exec = vec![Gene::StateFunc(int_add), Gene::StateFunc(exec_do_while)];
-        interpret_program(&mut test_state, STEP_LIMIT, MAX_STACK_SIZE);
+        test_state.exec = vec![
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::StateFunc(instruction!(exec_do_while)),
+        ];
+        interpret_program(
+            &mut test_state,
+            STEP_LIMIT,
+            MAX_STACK_SIZE,
+            MAX_OUTPUT_LENGTH,
+            MAX_POINTS,
+            Overflow::Checked,
+        )
+        .unwrap();
         assert_eq!(vec![1, 1, 2], test_state.int);
     }
 
+    #[test]
+    fn exec_combinator_test() {
+        let mut test_state = EMPTY_STATE;
+
+        test_state.exec = vec![Gene::GeneInt(1), Gene::GeneInt(2), Gene::GeneInt(3)];
+        exec_k(&mut test_state);
+        assert_eq!(vec![Gene::GeneInt(1), Gene::GeneInt(3)], test_state.exec);
+
+        test_state.exec = vec![
+            Gene::GeneInt(0),
+            Gene::GeneInt(1),
+            Gene::GeneInt(2),
+            Gene::GeneInt(3),
+        ];
+        exec_s(&mut test_state);
+        assert_eq!(
+            vec![
+                Gene::GeneInt(0),
+                Gene::Block(vec![Gene::GeneInt(2), Gene::GeneInt(1)]),
+                Gene::GeneInt(1),
+                Gene::GeneInt(3),
+            ],
+            test_state.exec
+        );
+
+        // Not enough items
+        test_state.exec = vec![Gene::GeneInt(1), Gene::GeneInt(2)];
+        exec_s(&mut test_state);
+        assert_eq!(vec![Gene::GeneInt(1), Gene::GeneInt(2)], test_state.exec);
+
+        test_state.exec = vec![Gene::GeneInt(1)];
+        exec_y(&mut test_state);
+        assert_eq!(
+            vec![
+                Gene::Block(vec![
+                    Gene::StateFunc(instruction!(exec_y)),
+                    Gene::GeneInt(1)
+                ]),
+                Gene::GeneInt(1),
+            ],
+            test_state.exec
+        );
+    }
+
+    #[test]
+    fn max_points_test() {
+        let mut test_state = EMPTY_STATE;
+        test_state.max_points = 3;
+
+        test_state.code = vec![Gene::GeneInt(1), Gene::GeneInt(2)];
+        code_combine(&mut test_state);
+        assert_eq!(
+            vec![Gene::Block(vec![Gene::GeneInt(2), Gene::GeneInt(1)])],
+            test_state.code
+        );
+
+        // A block of 4 points is over the limit
+        test_state.code.push(Gene::GeneInt(3));
+        code_combine(&mut test_state);
+        assert_eq!(
+            vec![
+                Gene::Block(vec![Gene::GeneInt(2), Gene::GeneInt(1)]),
+                Gene::GeneInt(3)
+            ],
+            test_state.code
+        );
+        code_wrap_block(&mut test_state);
+        assert_eq!(Gene::Block(vec![Gene::GeneInt(3)]), test_state.code[1]);
+        code_pop(&mut test_state);
+        code_wrap_block(&mut test_state);
+        assert_eq!(
+            vec![Gene::Block(vec![Gene::GeneInt(2), Gene::GeneInt(1)])],
+            test_state.code
+        );
+
+        test_state.code = vec![Gene::GeneInt(3), Gene::Block(vec![Gene::GeneInt(1)])];
+        test_state.int = vec![0];
+        code_insert(&mut test_state);
+        assert_eq!(
+            vec![Gene::Block(vec![Gene::GeneInt(3), Gene::GeneInt(1)])],
+            test_state.code
+        );
+        test_state.code.push(Gene::GeneInt(4));
+        test_state.int = vec![0];
+        code_insert(&mut test_state);
+        assert_eq!(2, test_state.code.len());
+        assert_eq!(vec![0], test_state.int);
+
+        test_state.exec = vec![
+            Gene::Block(vec![Gene::GeneInt(1), Gene::GeneInt(2)]),
+            Gene::GeneInt(3),
+            Gene::GeneInt(4),
+        ];
+        exec_s(&mut test_state);
+        assert_eq!(3, test_state.exec.len());
+        assert_eq!(Gene::GeneInt(4), test_state.exec[2]);
+    }
+
+    #[test]
+    fn exec_y_step_limit_test() {
+        let mut test_state = EMPTY_STATE;
+
+        // Adds one to the int every three steps until the step limit.
+        test_state.int = vec![0];
+        test_state.exec = vec![
+            Gene::StateFunc(instruction!(int_inc)),
+            Gene::StateFunc(instruction!(exec_y)),
+        ];
+        interpret_program(
+            &mut test_state,
+            STEP_LIMIT,
+            MAX_STACK_SIZE,
+            MAX_OUTPUT_LENGTH,
+            MAX_POINTS,
+            Overflow::Checked,
+        )
+        .unwrap();
+        assert_eq!(vec![333], test_state.int);
+        assert_eq!(2, test_state.exec.len());
+    }
+
     #[test]
     fn code_map_test() {
         let mut test_state = EMPTY_STATE;
@@ -882,10 +1161,10 @@ mod tests {
         test_state.exec = vec![Gene::GeneInt(-1)];
         code_map(&mut test_state);
         test_state.exec = vec![Gene::Block(vec![Gene::Block(vec![
-            Gene::StateFunc(code_from_exec),
+            Gene::StateFunc(instruction!(code_from_exec)),
             Gene::GeneInt(5),
             Gene::GeneInt(-1),
-            Gene::StateFunc(code_wrap_block),
+            Gene::StateFunc(instruction!(code_wrap_block)),
         ])])]
     }
 
@@ -964,7 +1243,7 @@ mod tests {
             Gene::Block(vec![
                 Gene::GeneInt(0),
                 Gene::GeneInt(4),
-                Gene::StateFunc(exec_do_range),
+                Gene::StateFunc(instruction!(exec_do_range)),
             ]),
             Gene::GeneInt(0),
         ];
@@ -976,7 +1255,7 @@ mod tests {
             Gene::Block(vec![
                 Gene::GeneInt(5),
                 Gene::GeneInt(4),
-                Gene::StateFunc(exec_do_range),
+                Gene::StateFunc(instruction!(exec_do_range)),
             ]),
             Gene::GeneInt(0),
         ];
@@ -12,6 +12,7 @@ macro_rules! pop {
             pub fn [< $in_stack _pop >] (state: &mut PushState) {
                 state.$in_stack.pop();
             }
+            register_instruction!([< $in_stack _pop >], [$in_stack], [], exec_blocks!($in_stack, 1));
         }
     };
 }
@@ -47,6 +48,9 @@ macro_rules! gene_map {
     (vector_char, $val:ident) => {
         Gene::GeneVectorChar($val)
     };
+    (name, $val:ident) => {
+        Gene::Name($val)
+    };
     (code, $val:ident) => {
         $val
     };
@@ -65,6 +69,7 @@ macro_rules! make_code {
                     state.code.push(push_val);
                 }
             }
+            register_instruction!([< code_from_ $stack >], [$stack], [code], exec_blocks!($stack, 1));
         }
     };
 }
@@ -74,8 +79,46 @@ fn _dup<T: Clone>(val: T) -> Option<Vec<T>> {
     Some(vec![val.clone(), val])
 }
 
-fn _dup_times<T: Clone>(amt: i128, val: T) -> Option<Vec<T>> {
-    Some(vec![val; amt as usize])
+/// Duplicates an item `amt` times, making at most `cap` copies so a huge
+/// `amt` can't exhaust memory. Does nothing when `amt` is below 1.
+fn _dup_times<T: Clone>(amt: i128, val: T, cap: usize) -> Option<Vec<T>> {
+    if amt < 1 {
+        return None;
+    }
+    let amt = amt.min(cap as i128) as usize;
+    Some(vec![val; amt])
+}
+
+/// The most copies `_dup_times` makes on a stack. Exec and code copies are
+/// program points, so they're capped at `max_points`. Other stacks get the
+/// room left under `max_stack_size` once the count and the item are popped.
+macro_rules! dup_times_cap {
+    (exec, $state:ident) => {
+        $state.max_points
+    };
+    (code, $state:ident) => {
+        $state.max_points
+    };
+    (int, $state:ident) => {
+        $state.max_stack_size.saturating_sub($state.int.len()) + 2
+    };
+    ($stack:ident, $state:ident) => {
+        $state.max_stack_size.saturating_sub($state.$stack.len()) + 1
+    };
+}
+
+/// Makes the `_dup_times` instruction for a stack, passing in its cap.
+macro_rules! dup_times {
+    ($stack:ident) => {
+        paste::item! {
+            pub fn [< $stack _dup_times >] (state: &mut PushState) {
+                let cap = dup_times_cap!($stack, state);
+                let capped = |amt, val| _dup_times(amt, val, cap);
+                rush_macro::run_instruction!(capped, $stack, state, int, $stack, ;);
+            }
+            register_instruction!([< $stack _dup_times >], [int, $stack], [$stack], exec_blocks!($stack, 1));
+        }
+    };
 }
 
 /// Swaps two values
@@ -105,6 +148,7 @@ macro_rules! flush_state {
             pub fn [< $in_stack _flush >] (state: &mut PushState) {
                 state.$in_stack.clear();
             }
+            register_instruction!([< $in_stack _flush >], [], [], 0);
         }
     };
 }
@@ -116,6 +160,7 @@ macro_rules! stack_depth {
             pub fn [< $in_stack _depth >] (state: &mut PushState) {
                 state.int.push(state.$in_stack.len() as i128)
             }
+            register_instruction!([< $in_stack _depth >], [], [int], 0);
         }
     };
 }
@@ -137,6 +182,12 @@ macro_rules! yank {
                 let item = state.$in_stack.remove(state.$in_stack.len() - 1 - idx);
                 state.$in_stack.push(item);
             }
+            register_instruction!(
+                [< $in_stack _yank >],
+                [int, $in_stack],
+                [$in_stack],
+                exec_blocks!($in_stack, 1)
+            );
         }
     };
 }
@@ -158,6 +209,12 @@ macro_rules! yank_dup {
                 let item = state.$in_stack[state.$in_stack.len() - 1 - idx].clone();
                 state.$in_stack.push(item);
             }
+            register_instruction!(
+                [< $in_stack _yank_dup >],
+                [int, $in_stack],
+                [$in_stack],
+                exec_blocks!($in_stack, 1)
+            );
         }
     };
 }
@@ -178,6 +235,7 @@ macro_rules! shove {
                 let item = state.$in_stack.pop().unwrap();
                 state.$in_stack.insert(state.$in_stack.len() - idx, item);
             }
+            register_instruction!([< $in_stack _shove >], [int, $in_stack], [$in_stack], exec_blocks!($in_stack, 1));
         }
     };
 }
@@ -198,6 +256,12 @@ macro_rules! shove_dup {
                 let item = state.$in_stack[state.$in_stack.len() - 1].clone();
                 state.$in_stack.insert(state.$in_stack.len() - idx, item);
             }
+            register_instruction!(
+                [< $in_stack _shove_dup >],
+                [int, $in_stack],
+                [$in_stack],
+                exec_blocks!($in_stack, 1)
+            );
         }
     };
 }
@@ -208,6 +272,7 @@ macro_rules! is_state_empty {
             pub fn [< $in_stack _is_empty >] (state: &mut PushState) {
                 state.boolean.push(state.$in_stack.is_empty());
             }
+            register_instruction!([< $in_stack _is_empty >], [], [boolean], 0);
         }
     };
 }
@@ -216,11 +281,11 @@ macro_rules! make_common_instructions {
     ($stack:ident) => {
         pop!($stack);
         make_code!($stack);
-        make_instruction_new_aux!(_dup, $stack, $stack, $stack);
-        make_instruction_new_aux!(_dup_times, $stack, $stack, int, $stack);
-        make_instruction_new_aux!(_swap, $stack, $stack, $stack, $stack);
-        make_instruction_new_aux!(_rotate, $stack, $stack, $stack, $stack, $stack);
-        make_instruction_new!(_equal, $stack, boolean, $stack, $stack);
+        make_instruction_new_aux!(_dup, $stack, $stack, $stack; exec_blocks!($stack, 1));
+        dup_times!($stack);
+        make_instruction_new_aux!(_swap, $stack, $stack, $stack, $stack; exec_blocks!($stack, 2));
+        make_instruction_new_aux!(_rotate, $stack, $stack, $stack, $stack, $stack; exec_blocks!($stack, 3));
+        make_instruction_new!(_equal, $stack, boolean, $stack, $stack; exec_blocks!($stack, 2));
         flush_state!($stack);
         stack_depth!($stack);
         yank!($stack);
@@ -241,9 +306,65 @@ make_common_instructions!(vector_float);
 make_common_instructions!(vector_string);
 make_common_instructions!(vector_boolean);
 make_common_instructions!(vector_char);
+make_common_instructions!(name);
 make_common_instructions!(code);
 make_common_instructions!(exec);
 
+/// Binds the top name to the top item of a stack. The name recalls
+/// the item whenever it is found on the exec stack afterwards.
+macro_rules! define {
+    ($in_stack:ident) => {
+        paste::item! {
+            pub fn [< $in_stack _define >] (state: &mut PushState) {
+                if state.name.is_empty() || state.$in_stack.is_empty() {
+                    return;
+                }
+                let name = state.name.pop().unwrap();
+                let val = state.$in_stack.pop().unwrap();
+                state.bindings.insert(name, gene_map!($in_stack, val));
+            }
+            register_instruction!(
+                [< $in_stack _define >],
+                [name, $in_stack],
+                [],
+                exec_blocks!($in_stack, 1)
+            );
+        }
+    };
+}
+
+define!(int);
+define!(float);
+define!(string);
+define!(boolean);
+define!(char);
+define!(vector_int);
+define!(vector_float);
+define!(vector_string);
+define!(vector_boolean);
+define!(vector_char);
+define!(code);
+define!(exec);
+
+/// Pushes the next name on the exec stack to the name stack without
+/// recalling it, so bound names can be bound again. In a plushy the name
+/// ends up at the front of the block this opens, so a name at the front of
+/// a block is quoted too and the rest of the block is left to run.
+pub fn name_quote(state: &mut PushState) {
+    match state.exec.pop() {
+        Some(Gene::Name(name)) => state.name.push(name),
+        Some(Gene::Block(mut block)) if matches!(block.first(), Some(Gene::Name(_))) => {
+            if let Gene::Name(name) = block.remove(0) {
+                state.name.push(name);
+            }
+            state.exec.extend(block.into_iter().rev());
+        }
+        Some(gene) => state.exec.push(gene),
+        None => (),
+    }
+}
+register_instruction!(name_quote, [exec], [name], 1);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -301,6 +422,43 @@ mod tests {
         test_state.int = vec![3];
         char_dup_times(&mut test_state);
         assert_eq!(vec!['a', 'b', 'b', 'b'], test_state.char);
+
+        // Counts below 1 do nothing
+        test_state.int = vec![0];
+        char_dup_times(&mut test_state);
+        test_state.int = vec![-5];
+        char_dup_times(&mut test_state);
+        assert_eq!(vec!['a', 'b', 'b', 'b'], test_state.char);
+        assert_eq!(vec![-5], test_state.int);
+    }
+
+    #[test]
+    fn dup_times_huge_count_test() {
+        let mut test_state = EMPTY_STATE;
+        test_state.max_points = 10;
+        test_state.max_stack_size = 5;
+
+        // Exec and code copies are capped at max_points
+        test_state.exec = vec![Gene::GeneInt(1)];
+        test_state.int = vec![100_000_000_000];
+        exec_dup_times(&mut test_state);
+        assert_eq!(vec![Gene::GeneInt(1); 10], test_state.exec);
+        assert!(test_state.int.is_empty());
+
+        test_state.code = vec![Gene::GeneInt(2)];
+        test_state.int = vec![i128::MAX];
+        code_dup_times(&mut test_state);
+        assert_eq!(vec![Gene::GeneInt(2); 10], test_state.code);
+
+        // Other stacks are filled up to max_stack_size
+        test_state.int = vec![1, i128::MAX];
+        int_dup_times(&mut test_state);
+        assert_eq!(vec![1; 5], test_state.int);
+
+        test_state.char = vec!['a', 'b'];
+        test_state.int = vec![i128::MAX];
+        char_dup_times(&mut test_state);
+        assert_eq!(vec!['a', 'b', 'b', 'b', 'b'], test_state.char);
     }
 
     #[test]
@@ -446,4 +604,60 @@ mod tests {
         int_is_empty(&mut test_state);
         assert_eq!(vec![true], test_state.boolean);
     }
+
+    #[test]
+    fn define_test() {
+        let mut test_state = EMPTY_STATE;
+
+        test_state.name = vec!["x".to_string(), "y".to_string()];
+        test_state.int = vec![1, 2];
+        int_define(&mut test_state);
+        assert_eq!(Some(&Gene::GeneInt(2)), test_state.bindings.get("y"));
+        assert_eq!(vec!["x".to_string()], test_state.name);
+        assert_eq!(vec![1], test_state.int);
+
+        test_state.exec = vec![Gene::Block(vec![Gene::GeneInt(3)])];
+        exec_define(&mut test_state);
+        assert_eq!(
+            Some(&Gene::Block(vec![Gene::GeneInt(3)])),
+            test_state.bindings.get("x")
+        );
+        assert!(test_state.exec.is_empty());
+
+        // No name to bind to
+        test_state.boolean = vec![true];
+        boolean_define(&mut test_state);
+        assert_eq!(vec![true], test_state.boolean);
+        assert_eq!(2, test_state.bindings.len());
+    }
+
+    #[test]
+    fn name_quote_test() {
+        let mut test_state = EMPTY_STATE;
+
+        test_state.exec = vec![Gene::GeneInt(1), Gene::Name("x".to_string())];
+        name_quote(&mut test_state);
+        assert_eq!(vec!["x".to_string()], test_state.name);
+        assert_eq!(vec![Gene::GeneInt(1)], test_state.exec);
+
+        // Not a name
+        name_quote(&mut test_state);
+        assert_eq!(vec!["x".to_string()], test_state.name);
+        assert_eq!(vec![Gene::GeneInt(1)], test_state.exec);
+
+        // A name at the front of a block, the rest of the block still runs
+        test_state.exec = vec![Gene::Block(vec![
+            Gene::Name("y".to_string()),
+            Gene::GeneInt(2),
+            Gene::GeneInt(3),
+        ])];
+        name_quote(&mut test_state);
+        assert_eq!(vec!["x".to_string(), "y".to_string()], test_state.name);
+        assert_eq!(vec![Gene::GeneInt(3), Gene::GeneInt(2)], test_state.exec);
+
+        // A block without a name in front is left alone
+        test_state.exec = vec![Gene::Block(vec![Gene::GeneInt(4)])];
+        name_quote(&mut test_state);
+        assert_eq!(vec![Gene::Block(vec![Gene::GeneInt(4)])], test_state.exec);
+    }
 }
@@ -1,24 +1,129 @@
 #[macro_use]
 pub mod macros {
-    /// Runs a function and ensures the necessary variables are extracted from a state without error
+    /// Adds an instruction to the `InstructionRegistry` along with the stacks it
+    /// pops from, the stacks it pushes to, and the number of blocks it opens.
+    ///
+    /// `register_instruction!(exec_when, [exec, boolean], [], 1)`
+    macro_rules! register_instruction {
+        ($func:ident, [$($in_stacks:ident), *], [$($out_stacks:ident), *], $blocks:expr) => {
+            paste::item! {
+                inventory::submit! {
+                    $crate::instructions::registry::InstructionInfo {
+                        name: stringify!($func),
+                        function: $func,
+                        input_stacks: &[$($crate::push::state::Stack::[< $in_stacks:camel >]), *],
+                        output_stacks: &[$($crate::push::state::Stack::[< $out_stacks:camel >]), *],
+                        blocks_opened: $blocks,
+                    }
+                }
+            }
+        };
+    }
+
+    /// The blocks an instruction opens when it is made for the exec stack.
+    /// The same instruction made for any other stack opens none.
+    macro_rules! exec_blocks {
+        (exec, $blocks:expr) => {
+            $blocks
+        };
+        ($stack:ident, $blocks:expr) => {
+            0
+        };
+    }
+
+    /// Runs a function and ensures the necessary variables are extracted from a state without error.
+    /// Pass `; blocks` at the end for instructions that open blocks.
     macro_rules! make_instruction_new {
-        ($func:ident, $prefix:ident, $out_stack:ident, $($stacks:ident), *) => {
+        ($func:ident, $prefix:ident, $out_stack:ident, $($stacks:ident), * ; $blocks:expr) => {
             paste::item! {
                 pub fn [< $prefix $func >] (state: &mut PushState) {
                     rush_macro::run_instruction!($func, $out_stack, state, $($stacks), *);
                 }
+                register_instruction!([< $prefix $func >], [$($stacks), *], [$out_stack], $blocks);
             }
         };
+        ($func:ident, $prefix:ident, $out_stack:ident, $($stacks:ident), *) => {
+            make_instruction_new!($func, $prefix, $out_stack, $($stacks), * ; 0);
+        };
     }
 
     /// Runs a function and ensures the necessary variables are extracted from a state without error while
-    /// returning multiple variables from the function
+    /// returning multiple variables from the function. Pass `; blocks` at the end for instructions
+    /// that open blocks.
     macro_rules! make_instruction_new_aux {
-        ($func:ident, $prefix:ident, $out_stack:ident, $($stacks:ident), *) => {
+        ($func:ident, $prefix:ident, $out_stack:ident, $($stacks:ident), * ; $blocks:expr) => {
             paste::item! {
                 pub fn [< $prefix $func >] (state: &mut PushState) {
                     rush_macro::run_instruction!($func, $out_stack, state, $($stacks), *, ;);
                 }
+                register_instruction!([< $prefix $func >], [$($stacks), *], [$out_stack], $blocks);
+            }
+        };
+        ($func:ident, $prefix:ident, $out_stack:ident, $($stacks:ident), *) => {
+            make_instruction_new_aux!($func, $prefix, $out_stack, $($stacks), * ; 0);
+        };
+    }
+
+    /// Like `make_instruction_new!` for arithmetic that can overflow. The function
+    /// takes the state's `Overflow` policy after the popped values.
+    macro_rules! make_instruction_overflow {
+        ($func:ident, $prefix:ident, $out_stack:ident, $stack0:ident) => {
+            paste::item! {
+                pub fn [< $prefix $func >] (state: &mut PushState) {
+                    let overflow = state.overflow;
+                    let with_overflow = |a| $func(a, overflow);
+                    rush_macro::run_instruction!(with_overflow, $out_stack, state, $stack0);
+                }
+                register_instruction!([< $prefix $func >], [$stack0], [$out_stack], 0);
+            }
+        };
+        ($func:ident, $prefix:ident, $out_stack:ident, $stack0:ident, $stack1:ident) => {
+            paste::item! {
+                pub fn [< $prefix $func >] (state: &mut PushState) {
+                    let overflow = state.overflow;
+                    let with_overflow = |a, b| $func(a, b, overflow);
+                    rush_macro::run_instruction!(with_overflow, $out_stack, state, $stack0, $stack1);
+                }
+                register_instruction!([< $prefix $func >], [$stack0, $stack1], [$out_stack], 0);
+            }
+        };
+    }
+
+    /// Like `make_instruction_new!` for instructions that build code. The
+    /// instruction is a noop when the code it would make has more than the
+    /// state's `max_points` points.
+    macro_rules! make_instruction_points {
+        ($func:ident, $prefix:ident, $out_stack:ident, $stack0:ident ; $blocks:expr) => {
+            paste::item! {
+                pub fn [< $prefix $func >] (state: &mut PushState) {
+                    let max_points = state.max_points;
+                    let within_points =
+                        |a| $func(a).filter(|gene: &Gene| gene.points() <= max_points);
+                    rush_macro::run_instruction!(within_points, $out_stack, state, $stack0);
+                }
+                register_instruction!([< $prefix $func >], [$stack0], [$out_stack], $blocks);
+            }
+        };
+        ($func:ident, $prefix:ident, $out_stack:ident, $stack0:ident, $stack1:ident ; $blocks:expr) => {
+            paste::item! {
+                pub fn [< $prefix $func >] (state: &mut PushState) {
+                    let max_points = state.max_points;
+                    let within_points =
+                        |a, b| $func(a, b).filter(|gene: &Gene| gene.points() <= max_points);
+                    rush_macro::run_instruction!(within_points, $out_stack, state, $stack0, $stack1);
+                }
+                register_instruction!([< $prefix $func >], [$stack0, $stack1], [$out_stack], $blocks);
+            }
+        };
+        ($func:ident, $prefix:ident, $out_stack:ident, $stack0:ident, $stack1:ident, $stack2:ident ; $blocks:expr) => {
+            paste::item! {
+                pub fn [< $prefix $func >] (state: &mut PushState) {
+                    let max_points = state.max_points;
+                    let within_points =
+                        |a, b, c| $func(a, b, c).filter(|gene: &Gene| gene.points() <= max_points);
+                    rush_macro::run_instruction!(within_points, $out_stack, state, $stack0, $stack1, $stack2);
+                }
+                register_instruction!([< $prefix $func >], [$stack0, $stack1, $stack2], [$out_stack], $blocks);
             }
         };
     }
@@ -33,6 +138,7 @@ pub mod macros {
                         state.$out_stack.push(result);
                     }
                 }
+                register_instruction!([< $prefix $func >], [], [$out_stack], 0);
             }
         };
     }
@@ -43,6 +149,8 @@ pub mod common;
 pub mod list;
 pub mod logical;
 pub mod numeric;
+pub mod print;
+pub mod registry;
 pub mod utils;
 pub mod vector;
 
@@ -6,52 +6,49 @@
 // Just don't know enough Rust yet ig.
 
 use crate::push::state::PushState;
-use num_traits::ops::checked::CheckedMul;
 use rust_decimal::Decimal;
 use std::cmp::{max, min};
-use std::ops::{Add, Div, Mul, Sub};
 
-use super::utils::{CastingTrait, NumericTrait};
+use super::utils::{CastingTrait, NumericTrait, Overflow};
 
 /// Adds two values together.
-fn _add<T>(a: T, b: T) -> Option<T>
+fn _add<T>(a: T, b: T, overflow: Overflow) -> Option<T>
 where
-    T: Add<Output = T>,
+    T: NumericTrait,
 {
-    Some(b + a)
+    b.add_with(a, overflow)
 }
 
 /// Subtracts two values from each other.
-fn _sub<T>(a: T, b: T) -> Option<T>
+fn _sub<T>(a: T, b: T, overflow: Overflow) -> Option<T>
 where
-    T: Sub<Output = T>,
+    T: NumericTrait,
 {
-    Some(b - a)
+    b.sub_with(a, overflow)
 }
 
 /// Multiplies two values with each other.
-fn _mult<T>(a: T, b: T) -> Option<T>
+fn _mult<T>(a: T, b: T, overflow: Overflow) -> Option<T>
 where
-    T: CheckedMul + Mul<Output = T> + Copy,
+    T: NumericTrait,
 {
-    // This is different from the numeric trait checked operators.
-    b.checked_mul(&a)
+    b.mul_with(a, overflow)
 }
 
 /// Divides two values from each other.
-fn _div<T>(a: T, b: T) -> Option<T>
+fn _div<T>(a: T, b: T, overflow: Overflow) -> Option<T>
 where
-    T: Div<Output = T> + NumericTrait,
+    T: NumericTrait,
 {
-    b.checked_div(a)
+    b.div_with(a, overflow)
 }
 
 /// Takes the remainder of two values
-fn _rem<T>(a: T, b: T) -> Option<T>
+fn _rem<T>(a: T, b: T, overflow: Overflow) -> Option<T>
 where
-    T: Div<Output = T> + Copy + NumericTrait,
+    T: NumericTrait,
 {
-    b.checked_mod(a)
+    b.rem_with(a, overflow)
 }
 
 /// Takes the max of two values
@@ -71,19 +68,19 @@ where
 }
 
 /// Increments a single value by 1
-fn _inc<T>(a: T) -> Option<T>
+fn _inc<T>(a: T, overflow: Overflow) -> Option<T>
 where
-    T: NumericTrait + Copy,
+    T: NumericTrait,
 {
-    Some(a.increment())
+    a.add_with(T::from_usize(1), overflow)
 }
 
 /// Decrements a single value by 1
-fn _dec<T>(a: T) -> Option<T>
+fn _dec<T>(a: T, overflow: Overflow) -> Option<T>
 where
     T: NumericTrait,
 {
-    Some(a.decrement())
+    a.sub_with(T::from_usize(1), overflow)
 }
 
 /// Checks if the 2nd to top value is less than the top value
@@ -197,7 +194,7 @@ fn _log<T>(a: T) -> Option<T>
 where
     T: NumericTrait,
 {
-    a.absolute().safe_log10()
+    a.safe_log10()
 }
 
 /// Takes the exp of a single value. Ints get truncated.
@@ -226,40 +223,40 @@ where
 }
 
 /// Takes the absolute value of the top number
-fn _abs<T>(a: T) -> Option<T>
+fn _abs<T>(a: T, overflow: Overflow) -> Option<T>
 where
     T: NumericTrait,
 {
-    Some(a.absolute())
+    a.abs_with(overflow)
 }
 
 /// Reverses the sign of the top number
-fn _sign_reverse<T>(a: T) -> Option<T>
+fn _sign_reverse<T>(a: T, overflow: Overflow) -> Option<T>
 where
     T: NumericTrait,
 {
-    Some(a.sign_reverse())
+    a.neg_with(overflow)
 }
 
 /// Squares the top number
-fn _square<T>(a: T) -> Option<T>
+fn _square<T>(a: T, overflow: Overflow) -> Option<T>
 where
-    T: NumericTrait,
+    T: NumericTrait + Copy,
 {
-    Some(a.square())
+    a.mul_with(a, overflow)
 }
 
 macro_rules! make_numeric_instructions {
     ($stack:ident) => {
-        make_instruction_new!(_add, $stack, $stack, $stack, $stack);
-        make_instruction_new!(_sub, $stack, $stack, $stack, $stack);
-        make_instruction_new!(_mult, $stack, $stack, $stack, $stack);
-        make_instruction_new!(_div, $stack, $stack, $stack, $stack);
-        make_instruction_new!(_rem, $stack, $stack, $stack, $stack);
+        make_instruction_overflow!(_add, $stack, $stack, $stack, $stack);
+        make_instruction_overflow!(_sub, $stack, $stack, $stack, $stack);
+        make_instruction_overflow!(_mult, $stack, $stack, $stack, $stack);
+        make_instruction_overflow!(_div, $stack, $stack, $stack, $stack);
+        make_instruction_overflow!(_rem, $stack, $stack, $stack, $stack);
         make_instruction_new!(_max, $stack, $stack, $stack, $stack);
         make_instruction_new!(_min, $stack, $stack, $stack, $stack);
-        make_instruction_new!(_inc, $stack, $stack, $stack);
-        make_instruction_new!(_dec, $stack, $stack, $stack);
+        make_instruction_overflow!(_inc, $stack, $stack, $stack);
+        make_instruction_overflow!(_dec, $stack, $stack, $stack);
         make_instruction_new!(_lt, $stack, boolean, $stack, $stack);
         make_instruction_new!(_gt, $stack, boolean, $stack, $stack);
         make_instruction_new!(_lte, $stack, boolean, $stack, $stack);
@@ -275,9 +272,9 @@ macro_rules! make_numeric_instructions {
         make_instruction_new!(_exp, $stack, $stack, $stack);
         make_instruction_new!(_sqrt, $stack, $stack, $stack);
         make_instruction_new!(_inv, $stack, $stack, $stack);
-        make_instruction_new!(_abs, $stack, $stack, $stack);
-        make_instruction_new!(_sign_reverse, $stack, $stack, $stack);
-        make_instruction_new!(_square, $stack, $stack, $stack);
+        make_instruction_overflow!(_abs, $stack, $stack, $stack);
+        make_instruction_overflow!(_sign_reverse, $stack, $stack, $stack);
+        make_instruction_overflow!(_square, $stack, $stack, $stack);
     };
 }
 
@@ -300,39 +297,51 @@ mod tests {
     /// Tests the _add function.
     #[test]
     fn add_test() {
-        assert_eq!(Some(3), _add(1, 2));
-        assert_eq!(Some(dec!(3.3)), _add(dec!(1.1), dec!(2.2)));
+        assert_eq!(Some(3), _add(1, 2, Overflow::Checked));
+        assert_eq!(
+            Some(dec!(3.3)),
+            _add(dec!(1.1), dec!(2.2), Overflow::Checked)
+        );
     }
 
     /// Tests the _sub function.
     #[test]
     fn sub_test() {
-        assert_eq!(Some(1), _sub(1, 2));
-        assert_eq!(Some(dec!(1.1)), _sub(dec!(1.1), dec!(2.2)));
+        assert_eq!(Some(1), _sub(1, 2, Overflow::Checked));
+        assert_eq!(
+            Some(dec!(1.1)),
+            _sub(dec!(1.1), dec!(2.2), Overflow::Checked)
+        );
     }
 
     /// Tests the _mult function.
     #[test]
     fn mult_test() {
-        assert_eq!(Some(20), _mult(5, 4));
-        assert_eq!(Some(dec!(2.42)), _mult(dec!(2.2), dec!(1.1)));
+        assert_eq!(Some(20), _mult(5, 4, Overflow::Checked));
+        assert_eq!(
+            Some(dec!(2.42)),
+            _mult(dec!(2.2), dec!(1.1), Overflow::Checked)
+        );
     }
 
     /// Tests the _div function
     #[test]
     fn div_test() {
-        assert_eq!(Some(5), _div(4, 20));
-        assert_eq!(Some(6), _div(3, 20));
-        assert_eq!(Some(dec!(1.375)), _div(dec!(1.6), dec!(2.2)));
-        assert_eq!(None, _div(0, 1));
+        assert_eq!(Some(5), _div(4, 20, Overflow::Checked));
+        assert_eq!(Some(6), _div(3, 20, Overflow::Checked));
+        assert_eq!(
+            Some(dec!(1.375)),
+            _div(dec!(1.6), dec!(2.2), Overflow::Checked)
+        );
+        assert_eq!(None, _div(0, 1, Overflow::Checked));
     }
 
     /// Tests the _rem function
     #[test]
     fn rem_test() {
-        assert_eq!(Some(2), _rem(3, 20));
-        assert_eq!(Some(0), _rem(20, 20));
-        assert_eq!(None, _rem(0, 9));
+        assert_eq!(Some(2), _rem(3, 20, Overflow::Checked));
+        assert_eq!(Some(0), _rem(20, 20, Overflow::Checked));
+        assert_eq!(None, _rem(0, 9, Overflow::Checked));
     }
 
     /// Tests the _max function
@@ -356,10 +365,10 @@ mod tests {
     /// Tests the _inc and _dec functions
     #[test]
     fn inc_dec_test() {
-        assert_eq!(Some(3), _inc(2));
-        assert_eq!(Some(9), _dec(10));
-        assert_eq!(Some(dec!(3.2)), _inc(dec!(2.2)));
-        assert_eq!(Some(dec!(4.6)), _dec(dec!(5.6)));
+        assert_eq!(Some(3), _inc(2, Overflow::Checked));
+        assert_eq!(Some(9), _dec(10, Overflow::Checked));
+        assert_eq!(Some(dec!(3.2)), _inc(dec!(2.2), Overflow::Checked));
+        assert_eq!(Some(dec!(4.6)), _dec(dec!(5.6), Overflow::Checked));
     }
 
     /// Tests the _lt, _gt, _lte, and _gte functions
@@ -787,4 +796,49 @@ mod tests {
         float_square(&mut test_state);
         assert_eq!(vec![dec!(16.0)], test_state.float);
     }
+
+    /// Tests each overflow policy on int arithmetic at i128::MAX.
+    #[test]
+    fn state_overflow() {
+        let mut test_state = EMPTY_STATE;
+
+        // Checked leaves the inputs alone
+        test_state.int = vec![i128::MAX, 1];
+        int_add(&mut test_state);
+        assert_eq!(vec![i128::MAX, 1], test_state.int);
+        test_state.int = vec![i128::MAX];
+        int_square(&mut test_state);
+        int_inc(&mut test_state);
+        assert_eq!(vec![i128::MAX], test_state.int);
+        test_state.int = vec![i128::MIN, -1];
+        int_div(&mut test_state);
+        assert_eq!(vec![i128::MIN, -1], test_state.int);
+
+        test_state.overflow = Overflow::Saturating;
+        test_state.int = vec![i128::MAX, 1];
+        int_add(&mut test_state);
+        assert_eq!(vec![i128::MAX], test_state.int);
+        int_square(&mut test_state);
+        assert_eq!(vec![i128::MAX], test_state.int);
+        test_state.int = vec![-2, i128::MAX];
+        int_mult(&mut test_state);
+        assert_eq!(vec![i128::MIN], test_state.int);
+        int_sign_reverse(&mut test_state);
+        assert_eq!(vec![i128::MAX], test_state.int);
+
+        test_state.overflow = Overflow::Wrapping;
+        test_state.int = vec![i128::MAX, 1];
+        int_add(&mut test_state);
+        assert_eq!(vec![i128::MIN], test_state.int);
+        int_dec(&mut test_state);
+        assert_eq!(vec![i128::MAX], test_state.int);
+        int_square(&mut test_state);
+        assert_eq!(vec![1], test_state.int);
+        test_state.int = vec![i128::MIN];
+        int_abs(&mut test_state);
+        assert_eq!(vec![i128::MIN], test_state.int);
+        test_state.int = vec![i128::MIN, -1];
+        int_rem(&mut test_state);
+        assert_eq!(vec![0], test_state.int);
+    }
 }
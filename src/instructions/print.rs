@@ -0,0 +1,109 @@
+//! # Print Instructions
+//!
+//! This file holds instructions that pop an item and append it as text to
+//! `PushState::output`. Floats keep their scale, strings and chars are
+//! written as is, and vectors are written like `[1 2 3]`.
+
+use crate::push::state::PushState;
+
+/// Text of a single item.
+fn _text<T: ToString>(val: T) -> String {
+    val.to_string()
+}
+
+/// Text of a string.
+fn _string_text(val: Vec<char>) -> String {
+    val.into_iter().collect()
+}
+
+/// Text of a vector, the items separated by spaces inside brackets.
+fn _vector_text<T: ToString>(vals: Vec<T>) -> String {
+    let items: Vec<String> = vals.iter().map(|val| val.to_string()).collect();
+    format!("[{}]", items.join(" "))
+}
+
+/// Text of a vector of strings.
+fn _vector_string_text(vals: Vec<Vec<char>>) -> String {
+    _vector_text(vals.into_iter().map(_string_text).collect())
+}
+
+/// Makes a print instruction for a stack. `$to_text` turns the popped item
+/// into the text to write.
+macro_rules! make_print {
+    ($stack:ident, $to_text:ident) => {
+        paste::item! {
+            pub fn [< print_ $stack >] (state: &mut PushState) {
+                if let Some(val) = state.$stack.pop() {
+                    state.output.push_str(&$to_text(val));
+                }
+            }
+            register_instruction!([< print_ $stack >], [$stack], [print], 0);
+        }
+    };
+}
+
+make_print!(int, _text);
+make_print!(float, _text);
+make_print!(string, _string_text);
+make_print!(boolean, _text);
+make_print!(char, _text);
+make_print!(vector_int, _vector_text);
+make_print!(vector_float, _vector_text);
+make_print!(vector_string, _vector_string_text);
+make_print!(vector_boolean, _vector_text);
+make_print!(vector_char, _vector_text);
+
+/// Writes a newline.
+pub fn print_newline(state: &mut PushState) {
+    state.output.push('\n');
+}
+register_instruction!(print_newline, [], [print], 0);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::push::state::EMPTY_STATE;
+    use rust_decimal::dec;
+
+    #[test]
+    fn print_test() {
+        let mut test_state = EMPTY_STATE;
+
+        test_state.int = vec![1, -2];
+        print_int(&mut test_state);
+        assert_eq!("-2", test_state.output);
+        assert_eq!(vec![1], test_state.int);
+
+        test_state.float = vec![dec!(1.50)];
+        print_float(&mut test_state);
+        print_newline(&mut test_state);
+        test_state.string = vec![vec!['h', 'i']];
+        print_string(&mut test_state);
+        test_state.char = vec![' '];
+        print_char(&mut test_state);
+        test_state.boolean = vec![false];
+        print_boolean(&mut test_state);
+        assert_eq!("-21.50\nhi false", test_state.output);
+
+        // Nothing to print
+        print_vector_int(&mut test_state);
+        assert_eq!("-21.50\nhi false", test_state.output);
+    }
+
+    #[test]
+    fn print_vector_test() {
+        let mut test_state = EMPTY_STATE;
+
+        test_state.vector_int = vec![vec![1, 2, 3]];
+        print_vector_int(&mut test_state);
+        test_state.vector_float = vec![vec![dec!(0.5)]];
+        print_vector_float(&mut test_state);
+        test_state.vector_string = vec![vec![vec!['a'], vec!['b', 'c']]];
+        print_vector_string(&mut test_state);
+        test_state.vector_boolean = vec![vec![]];
+        print_vector_boolean(&mut test_state);
+        test_state.vector_char = vec![vec!['x', 'y']];
+        print_vector_char(&mut test_state);
+        assert_eq!("[1 2 3][0.5][a bc][][x y]", test_state.output);
+    }
+}
@@ -0,0 +1,172 @@
+//! # Instruction Registry
+//!
+//! Every instruction registers itself at compile time with the
+//! `register_instruction!` macro, which `make_instruction_new!` and the other
+//! instruction macros call for you. Defining an instruction is all it takes
+//! to make it available, there is no list to regenerate.
+//!
+//! Each entry holds the instruction's name, its function, the stacks it pops
+//! from, the stacks it pushes to, and how many blocks it opens in a plushy.
+//! Instructions are named after the stack they belong to, `int_add` belongs
+//! to `Stack::Int` for example.
+
+use crate::push::state::{Instruction, PushState, Stack};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Everything known about an instruction.
+#[derive(Debug)]
+pub struct InstructionInfo {
+    pub name: &'static str, // Name of the instruction, also its function name
+    pub function: fn(&mut PushState), // The instruction itself
+    pub input_stacks: &'static [Stack], // Stacks popped from, in the order they're popped
+    pub output_stacks: &'static [Stack], // Stacks pushed to
+    pub blocks_opened: u8,  // Blocks opened when the instruction is in a plushy
+}
+
+inventory::collect!(InstructionInfo);
+
+impl InstructionInfo {
+    /// The stack this instruction belongs to, taken from its name.
+    pub fn stack(&self) -> Option<Stack> {
+        Stack::ALL
+            .into_iter()
+            .find(|stack| self.name.starts_with(&format!("{}_", stack.name())))
+    }
+
+    /// The instruction as it's held in a gene.
+    pub fn instruction(&self) -> Instruction {
+        Instruction {
+            name: self.name,
+            function: self.function,
+        }
+    }
+}
+
+/// All registered instructions, sorted by name.
+pub struct InstructionRegistry {
+    instructions: Vec<&'static InstructionInfo>,
+    by_name: HashMap<&'static str, &'static InstructionInfo>,
+}
+
+impl InstructionRegistry {
+    fn new() -> Self {
+        let mut instructions: Vec<&'static InstructionInfo> =
+            inventory::iter::<InstructionInfo>.into_iter().collect();
+        instructions.sort_by_key(|info| info.name);
+
+        let mut by_name = HashMap::with_capacity(instructions.len());
+        for info in instructions.iter().copied() {
+            if by_name.insert(info.name, info).is_some() {
+                panic!("Instruction registered twice: {}", info.name);
+            }
+        }
+
+        Self {
+            instructions,
+            by_name,
+        }
+    }
+
+    /// Every registered instruction, sorted by name.
+    pub fn all(&self) -> &[&'static InstructionInfo] {
+        &self.instructions
+    }
+
+    /// Looks an instruction up by name.
+    pub fn get(&self, name: &str) -> Option<&'static InstructionInfo> {
+        self.by_name.get(name).copied()
+    }
+
+    /// The instructions belonging to `stack`, sorted by name.
+    pub fn stack_instructions(&self, stack: Stack) -> Vec<fn(&mut PushState)> {
+        self.instructions
+            .iter()
+            .filter(|info| info.stack() == Some(stack))
+            .map(|info| info.function)
+            .collect()
+    }
+}
+
+/// The registry of every instruction in rush.
+pub static INSTRUCTION_REGISTRY: LazyLock<InstructionRegistry> =
+    LazyLock::new(InstructionRegistry::new);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::code::{code_noop, exec_noop};
+    use crate::instructions::numeric::int_add;
+
+    #[test]
+    fn registry_lookup_test() {
+        let info = INSTRUCTION_REGISTRY.get("int_add").unwrap();
+        assert!(std::ptr::fn_addr_eq(
+            info.function,
+            int_add as fn(&mut PushState)
+        ));
+        assert_eq!(&[Stack::Int, Stack::Int], info.input_stacks);
+        assert_eq!(&[Stack::Int], info.output_stacks);
+        assert_eq!(0, info.blocks_opened);
+        assert_eq!(Some(Stack::Int), info.stack());
+
+        assert_eq!(instruction!(int_add), info.instruction());
+        assert!(INSTRUCTION_REGISTRY.get("int_not_real").is_none());
+    }
+
+    #[test]
+    fn registry_blocks_test() {
+        let blocks = |name| INSTRUCTION_REGISTRY.get(name).unwrap().blocks_opened;
+        assert_eq!(1, blocks("exec_dup"));
+        assert_eq!(2, blocks("exec_if"));
+        assert_eq!(1, blocks("vector_int_iterate"));
+        assert_eq!(0, blocks("int_add"));
+    }
+
+    #[test]
+    fn same_body_instructions_test() {
+        // code_noop and exec_noop have the same empty body, so they may share
+        // an address. Their genes still differ by name.
+        assert_ne!(instruction!(code_noop), instruction!(exec_noop));
+        assert_eq!("exec_noop", instruction!(exec_noop).name);
+    }
+
+    #[test]
+    fn registry_stacks_test() {
+        let registry = &*INSTRUCTION_REGISTRY;
+        assert!(registry.all().windows(2).all(|w| w[0].name < w[1].name));
+        for info in registry.all() {
+            assert!(info.stack().is_some(), "{} has no stack", info.name);
+        }
+        let names = |stack| {
+            registry
+                .all()
+                .iter()
+                .filter(|info| info.stack() == Some(stack))
+                .map(|info| info.name)
+                .collect::<Vec<&str>>()
+        };
+        assert!(names(Stack::VectorInt).contains(&"vector_int_iterate"));
+        assert!(
+            names(Stack::Int)
+                .iter()
+                .all(|name| name.starts_with("int_"))
+        );
+    }
+    #[test]
+    fn exec_blocks_test() {
+        // Every item an instruction pops from exec is a block in a plushy.
+        for info in INSTRUCTION_REGISTRY.all() {
+            let exec_inputs = info
+                .input_stacks
+                .iter()
+                .filter(|stack| **stack == Stack::Exec)
+                .count();
+            assert_eq!(
+                exec_inputs, info.blocks_opened as usize,
+                "{} pops {} exec items but opens {} blocks",
+                info.name, exec_inputs, info.blocks_opened
+            );
+        }
+    }
+}
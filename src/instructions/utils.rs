@@ -1,7 +1,21 @@
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::ops::{Add, Div, Mul};
 
+/// What int arithmetic does when a result doesn't fit in an `i128`.
+/// Floats ignore the policy, overflowing float arithmetic is always a noop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Overflow {
+    /// The instruction does nothing, leaving its inputs on the stacks.
+    #[default]
+    Checked,
+    /// The result is clamped to `i128::MIN` or `i128::MAX`.
+    Saturating,
+    /// The result wraps around.
+    Wrapping,
+}
+
 /// This trait houses various methods for making instructions
 /// more generic instead of declaring a separate function for each
 /// stack. In a way I'm doing that here, but in a more Rusty way.
@@ -11,10 +25,13 @@ use std::ops::{Add, Div, Mul};
 pub trait NumericTrait:
     Sized + Add<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Ord
 {
-    fn checked_div(self, v: Self) -> Option<Self>;
-    fn checked_mod(self, v: Self) -> Option<Self>;
-    fn increment(self) -> Self;
-    fn decrement(self) -> Self;
+    fn add_with(self, v: Self, overflow: Overflow) -> Option<Self>;
+    fn sub_with(self, v: Self, overflow: Overflow) -> Option<Self>;
+    fn mul_with(self, v: Self, overflow: Overflow) -> Option<Self>;
+    fn div_with(self, v: Self, overflow: Overflow) -> Option<Self>;
+    fn rem_with(self, v: Self, overflow: Overflow) -> Option<Self>;
+    fn abs_with(self, overflow: Overflow) -> Option<Self>;
+    fn neg_with(self, overflow: Overflow) -> Option<Self>;
     fn safe_sin(self) -> Option<Self>;
     fn safe_cos(self) -> Option<Self>;
     fn safe_tan(self) -> Option<Self>;
@@ -23,24 +40,31 @@ pub trait NumericTrait:
     fn absolute(self) -> Self;
     fn safe_log10(self) -> Option<Self>;
     fn safe_sqrt(self) -> Option<Self>;
-    fn sign_reverse(self) -> Self;
-    fn square(self) -> Self;
     fn zero() -> Self;
     fn from_usize(num: usize) -> Self;
 }
 
 impl NumericTrait for Decimal {
-    fn checked_div(self, v: Self) -> Option<Self> {
-        if v == dec!(0.0) { None } else { Some(self / v) }
+    fn add_with(self, v: Self, _overflow: Overflow) -> Option<Self> {
+        self.checked_add(v)
+    }
+    fn sub_with(self, v: Self, _overflow: Overflow) -> Option<Self> {
+        self.checked_sub(v)
+    }
+    fn mul_with(self, v: Self, _overflow: Overflow) -> Option<Self> {
+        self.checked_mul(v)
+    }
+    fn div_with(self, v: Self, _overflow: Overflow) -> Option<Self> {
+        self.checked_div(v)
     }
-    fn checked_mod(self, v: Self) -> Option<Self> {
-        if v == dec!(0.0) { None } else { Some(self % v) }
+    fn rem_with(self, v: Self, _overflow: Overflow) -> Option<Self> {
+        self.checked_rem(v)
     }
-    fn increment(self) -> Self {
-        self + dec!(1.0)
+    fn abs_with(self, _overflow: Overflow) -> Option<Self> {
+        Some(self.abs())
     }
-    fn decrement(self) -> Self {
-        self - dec!(1.0)
+    fn neg_with(self, _overflow: Overflow) -> Option<Self> {
+        Some(-self)
     }
     fn safe_sin(self) -> Option<Self> {
         self.checked_sin()
@@ -66,12 +90,6 @@ impl NumericTrait for Decimal {
     fn safe_sqrt(self) -> Option<Self> {
         self.absolute().sqrt()
     }
-    fn sign_reverse(self) -> Self {
-        self * dec!(-1)
-    }
-    fn square(self) -> Self {
-        self * self
-    }
     fn zero() -> Self {
         dec!(0.0)
     }
@@ -81,17 +99,57 @@ impl NumericTrait for Decimal {
 }
 
 impl NumericTrait for i128 {
-    fn checked_div(self, v: Self) -> Option<Self> {
-        if v == 0 { None } else { Some(self / v) }
+    fn add_with(self, v: Self, overflow: Overflow) -> Option<Self> {
+        match overflow {
+            Overflow::Checked => self.checked_add(v),
+            Overflow::Saturating => Some(self.saturating_add(v)),
+            Overflow::Wrapping => Some(self.wrapping_add(v)),
+        }
     }
-    fn checked_mod(self, v: Self) -> Option<Self> {
-        if v == 0 { None } else { Some(self % v) }
+    fn sub_with(self, v: Self, overflow: Overflow) -> Option<Self> {
+        match overflow {
+            Overflow::Checked => self.checked_sub(v),
+            Overflow::Saturating => Some(self.saturating_sub(v)),
+            Overflow::Wrapping => Some(self.wrapping_sub(v)),
+        }
     }
-    fn increment(self) -> Self {
-        self + 1
+    fn mul_with(self, v: Self, overflow: Overflow) -> Option<Self> {
+        match overflow {
+            Overflow::Checked => self.checked_mul(v),
+            Overflow::Saturating => Some(self.saturating_mul(v)),
+            Overflow::Wrapping => Some(self.wrapping_mul(v)),
+        }
     }
-    fn decrement(self) -> Self {
-        self - 1
+    /// Dividing by 0 is a noop under every policy.
+    fn div_with(self, v: Self, overflow: Overflow) -> Option<Self> {
+        match overflow {
+            _ if v == 0 => None,
+            Overflow::Checked => self.checked_div(v),
+            Overflow::Saturating => Some(self.saturating_div(v)),
+            Overflow::Wrapping => Some(self.wrapping_div(v)),
+        }
+    }
+    /// Only `i128::MIN % -1` overflows, its true result 0 can't saturate.
+    fn rem_with(self, v: Self, overflow: Overflow) -> Option<Self> {
+        match overflow {
+            _ if v == 0 => None,
+            Overflow::Checked => self.checked_rem(v),
+            Overflow::Saturating | Overflow::Wrapping => Some(self.wrapping_rem(v)),
+        }
+    }
+    fn abs_with(self, overflow: Overflow) -> Option<Self> {
+        match overflow {
+            Overflow::Checked => self.checked_abs(),
+            Overflow::Saturating => Some(self.saturating_abs()),
+            Overflow::Wrapping => Some(self.wrapping_abs()),
+        }
+    }
+    fn neg_with(self, overflow: Overflow) -> Option<Self> {
+        match overflow {
+            Overflow::Checked => self.checked_neg(),
+            Overflow::Saturating => Some(self.saturating_neg()),
+            Overflow::Wrapping => Some(self.wrapping_neg()),
+        }
     }
     /// Casts the i128 to a Decimal and takes the checked_sin
     /// of the value. Casts the calculated value back to an i128.
@@ -122,12 +180,6 @@ impl NumericTrait for i128 {
     fn safe_sqrt(self) -> Option<Self> {
         Decimal::from_i128(self)?.absolute().sqrt()?.to_i128()
     }
-    fn sign_reverse(self) -> Self {
-        -1 * self
-    }
-    fn square(self) -> Self {
-        self * self
-    }
     fn zero() -> Self {
         0
     }
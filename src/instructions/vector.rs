@@ -1,4 +1,4 @@
-use crate::instructions::utils::NumericTrait;
+use crate::instructions::utils::{NumericTrait, Overflow};
 use crate::push::state::{Gene, PushState};
 use rust_decimal::Decimal;
 use std::collections::{HashMap, HashSet};
@@ -410,12 +410,18 @@ macro_rules! make_iterate {
                 } else {
                     let top_exec = state.exec[state.exec.len() - 1].clone();
                     let first_prim = first_vec[0].clone();
-                    state.exec.push(Gene::StateFunc([< $vec_stack _iterate >]));
+                    state.exec.push(Gene::StateFunc(instruction!([< $vec_stack _iterate >])));
                     state.exec.push(Gene::$vec_gene(first_vec[1..].to_vec()));
                     state.exec.push(top_exec);
                     state.$prim_stack.push(first_prim);
                 }
             }
+            register_instruction!(
+                [< $vec_stack _iterate >],
+                [$vec_stack, exec],
+                [exec, $prim_stack],
+                1
+            );
         }
     };
 }
@@ -463,15 +469,12 @@ where
 }
 
 /// Takes the mean of a vector
-fn _mean<T: NumericTrait + Clone>(vals: Vec<T>) -> Option<T> {
+fn _mean<T: NumericTrait + Clone>(vals: Vec<T>, overflow: Overflow) -> Option<T> {
     if vals.is_empty() {
         return Some(T::zero());
     }
-    let mut fin_num = T::zero();
-    for num in vals.clone().into_iter() {
-        fin_num = fin_num + num;
-    }
-    Some(fin_num.div(T::from_usize(vals.len())))
+    let len = T::from_usize(vals.len());
+    _sum(vals, overflow)?.div_with(len, overflow)
 }
 
 /// Takes the max of a vector
@@ -491,13 +494,13 @@ fn _minimum<T: NumericTrait>(vals: Vec<T>) -> Option<T> {
 }
 
 /// Takes the sum of a vector
-fn _sum<T: NumericTrait + Clone>(vals: Vec<T>) -> Option<T> {
+fn _sum<T: NumericTrait + Clone>(vals: Vec<T>, overflow: Overflow) -> Option<T> {
     if vals.is_empty() {
         return Some(T::zero());
     }
     let mut fin_num = T::zero();
-    for num in vals.clone().into_iter() {
-        fin_num = fin_num + num;
+    for num in vals.into_iter() {
+        fin_num = fin_num.add_with(num, overflow)?;
     }
     Some(fin_num)
 }
@@ -518,26 +521,26 @@ fn _mode<T: NumericTrait + Clone + Hash + Copy>(vals: Vec<T>) -> Option<T> {
 }
 
 /// Adds the squares of all values in a vector and then takes the square root
-fn _two_norm<T: NumericTrait + Clone>(vals: Vec<T>) -> Option<T> {
+fn _two_norm<T: NumericTrait + Clone>(vals: Vec<T>, overflow: Overflow) -> Option<T> {
     if vals.is_empty() {
         return Some(T::zero());
     }
     let mut fin_num = T::zero();
-    for num in vals.clone().into_iter() {
-        fin_num = fin_num + (num.clone() * num);
+    for num in vals.into_iter() {
+        fin_num = fin_num.add_with(num.clone().mul_with(num, overflow)?, overflow)?;
     }
     fin_num.safe_sqrt()
 }
 
 /// Takes the cumulative sum of a vector
-fn _cumulative_sum<T: NumericTrait + Clone>(vals: Vec<T>) -> Option<Vec<T>> {
+fn _cumulative_sum<T: NumericTrait + Clone>(vals: Vec<T>, overflow: Overflow) -> Option<Vec<T>> {
     if vals.is_empty() {
         return Some(vec![]);
     }
     let mut fin_num = T::zero();
     let mut ret_vec = vec![];
-    for num in vals.clone().into_iter() {
-        fin_num = fin_num + num;
+    for num in vals.into_iter() {
+        fin_num = fin_num.add_with(num, overflow)?;
         ret_vec.push(fin_num.clone());
     }
     Some(ret_vec)
@@ -621,13 +624,13 @@ macro_rules! make_numeric_vector_instructions {
     ($stack:ident, $prim_stack:ident) => {
         make_instruction_new!(_sort, $stack, $stack, $stack);
         make_instruction_new!(_sort_reverse, $stack, $stack, $stack);
-        make_instruction_new!(_mean, $stack, $prim_stack, $stack);
+        make_instruction_overflow!(_mean, $stack, $prim_stack, $stack);
         make_instruction_new!(_maximum, $stack, $prim_stack, $stack);
         make_instruction_new!(_minimum, $stack, $prim_stack, $stack);
-        make_instruction_new!(_sum, $stack, $prim_stack, $stack);
+        make_instruction_overflow!(_sum, $stack, $prim_stack, $stack);
         make_instruction_new!(_mode, $stack, $prim_stack, $stack);
-        make_instruction_new!(_two_norm, $stack, $prim_stack, $stack);
-        make_instruction_new!(_cumulative_sum, $stack, $stack, $stack);
+        make_instruction_overflow!(_two_norm, $stack, $prim_stack, $stack);
+        make_instruction_overflow!(_cumulative_sum, $stack, $stack, $stack);
     };
 }
 
@@ -1308,10 +1311,10 @@ mod tests {
 
         test_state.vector_int = vec![vec![0, 1, 2, 3, 4, 5, 2]];
         test_state.exec = vec![
-            Gene::StateFunc(int_inc),
-            Gene::StateFunc(vector_int_iterate),
+            Gene::StateFunc(instruction!(int_inc)),
+            Gene::StateFunc(instruction!(vector_int_iterate)),
         ];
-        interpret_program(&mut test_state, 1000, 1000);
+        interpret_program(&mut test_state, 1000, 1000, 1000, 100, Overflow::Checked).unwrap();
         assert_eq!(vec![1, 2, 3, 4, 5, 6, 3], test_state.int);
     }
 
@@ -1474,4 +1477,36 @@ mod tests {
         vector_int_cumulative_sum(&mut test_state);
         assert_eq!(vec![vec![0, 1, 3, 6, 10, 15, 17]], test_state.vector_int);
     }
+
+    #[test]
+    fn sum_overflow_test() {
+        let mut test_state = EMPTY_STATE;
+
+        // Checked leaves the vector alone
+        test_state.vector_int = vec![vec![i128::MAX, 1, -1]];
+        vector_int_sum(&mut test_state);
+        vector_int_cumulative_sum(&mut test_state);
+        assert_eq!(vec![vec![i128::MAX, 1, -1]], test_state.vector_int);
+        assert!(test_state.int.is_empty());
+
+        test_state.overflow = Overflow::Saturating;
+        vector_int_cumulative_sum(&mut test_state);
+        assert_eq!(
+            vec![vec![i128::MAX, i128::MAX, i128::MAX - 1]],
+            test_state.vector_int
+        );
+        test_state.vector_int = vec![vec![i128::MAX, 1, -1]];
+        vector_int_sum(&mut test_state);
+        assert_eq!(vec![i128::MAX - 1], test_state.int);
+
+        test_state.overflow = Overflow::Wrapping;
+        test_state.vector_int = vec![vec![i128::MAX, 1, -1]];
+        vector_int_cumulative_sum(&mut test_state);
+        assert_eq!(
+            vec![vec![i128::MAX, i128::MIN, i128::MAX]],
+            test_state.vector_int
+        );
+        vector_int_sum(&mut test_state);
+        assert_eq!(vec![i128::MAX - 1, i128::MAX - 1], test_state.int);
+    }
 }
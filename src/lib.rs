@@ -1,3 +1,15 @@
+/// Makes the `Instruction` for an instruction function, keeping its registered
+/// name: `Gene::StateFunc(instruction!(int_add))`.
+#[macro_export]
+macro_rules! instruction {
+    ($func:ident) => {
+        $crate::push::state::Instruction {
+            name: stringify!($func),
+            function: $func,
+        }
+    };
+}
+
 pub mod gp;
 pub mod instructions;
 pub mod push;
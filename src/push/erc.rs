@@ -0,0 +1,166 @@
+//! # Ephemeral Random Constants
+//!
+//! An ERC describes a kind of literal rather than a fixed value. ERCs sit in
+//! the gene pool as `Gene::Erc`, and every time one is sampled into a plushy
+//! it is replaced with a fresh random literal. ERCs never appear in plushies
+//! or push programs themselves.
+
+use crate::push::state::Gene;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A kind of random literal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Erc {
+    /// An int in `min..=max`.
+    Int { min: i128, max: i128 },
+    /// A float in `min..=max` rounded to `places` decimal places.
+    Float {
+        min: Decimal,
+        max: Decimal,
+        places: u32,
+    },
+    /// `true` or `false`.
+    Boolean,
+    /// A char from the alphabet.
+    Char(Vec<char>),
+    /// A string of chars from the alphabet, 0 to `max_len` long.
+    String { alphabet: Vec<char>, max_len: usize },
+    /// `tag_exec_N` or `tag_int_N` with `N` in `0..=max_tag`.
+    Tag { max_tag: u32 },
+    /// `tagged_N` with `N` in `0..=max_tag`.
+    Tagged { max_tag: u32 },
+    /// A name from a pool of `pool_size` names, `n0` to `n{pool_size - 1}`.
+    /// A small pool makes it likely a bound name is used again.
+    Name { pool_size: u32 },
+}
+
+impl Erc {
+    /// Makes a random literal of this kind.
+    pub fn generate(&self, rng: &mut impl Rng) -> Gene {
+        match self {
+            Erc::Int { min, max } => Gene::GeneInt(rng.random_range(*min..=*max)),
+            Erc::Float { min, max, places } => {
+                let frac = Decimal::from_f64(rng.random::<f64>()).unwrap();
+                Gene::GeneFloat((min + (max - min) * frac).round_dp(*places))
+            }
+            Erc::Boolean => Gene::GeneBoolean(rng.random()),
+            Erc::Char(alphabet) => Gene::GeneChar(random_char(alphabet, rng)),
+            Erc::String { alphabet, max_len } => {
+                let len = rng.random_range(0..=*max_len);
+                Gene::GeneString((0..len).map(|_| random_char(alphabet, rng)).collect())
+            }
+            Erc::Tag { max_tag } => {
+                let tag = rng.random_range(0..=*max_tag);
+                if rng.random() {
+                    Gene::TagExec(tag)
+                } else {
+                    Gene::TagInt(tag)
+                }
+            }
+            Erc::Tagged { max_tag } => Gene::Tagged(rng.random_range(0..=*max_tag)),
+            Erc::Name { pool_size } => Gene::Name(format!("n{}", rng.random_range(0..*pool_size))),
+        }
+    }
+
+    /// The name of this kind of ERC.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Erc::Int { .. } => "erc_int",
+            Erc::Float { .. } => "erc_float",
+            Erc::Boolean => "erc_boolean",
+            Erc::Char(_) => "erc_char",
+            Erc::String { .. } => "erc_string",
+            Erc::Tag { .. } => "erc_tag",
+            Erc::Tagged { .. } => "erc_tagged",
+            Erc::Name { .. } => "erc_name",
+        }
+    }
+}
+
+fn random_char(alphabet: &[char], rng: &mut impl Rng) -> char {
+    *alphabet.choose(rng).expect("ERC alphabet is empty")
+}
+
+/// Replaces an ERC gene with a fresh literal. Every other gene is returned as is.
+pub fn instantiate(gene: Gene, rng: &mut impl Rng) -> Gene {
+    match gene {
+        Gene::Erc(erc) => erc.generate(rng),
+        gene => gene,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use rust_decimal::dec;
+
+    #[test]
+    fn generate_test() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let int_erc = Erc::Int { min: -3, max: 3 };
+        for _ in 0..100 {
+            match int_erc.generate(&mut rng) {
+                Gene::GeneInt(val) => assert!((-3..=3).contains(&val)),
+                gene => panic!("Expected an int, got {:?}", gene),
+            }
+            let float_erc = Erc::Float {
+                min: dec!(-1.0),
+                max: dec!(1.0),
+                places: 2,
+            };
+            match float_erc.generate(&mut rng) {
+                Gene::GeneFloat(val) => {
+                    assert!(val >= dec!(-1.0) && val <= dec!(1.0));
+                    assert!(val.scale() <= 2);
+                }
+                gene => panic!("Expected a float, got {:?}", gene),
+            }
+            match Erc::Char(vec!['a', 'b']).generate(&mut rng) {
+                Gene::GeneChar(val) => assert!(val == 'a' || val == 'b'),
+                gene => panic!("Expected a char, got {:?}", gene),
+            }
+            let string_erc = Erc::String {
+                alphabet: vec!['x'],
+                max_len: 4,
+            };
+            match string_erc.generate(&mut rng) {
+                Gene::GeneString(val) => {
+                    assert!(val.len() <= 4);
+                    assert!(val.iter().all(|c| *c == 'x'));
+                }
+                gene => panic!("Expected a string, got {:?}", gene),
+            }
+        }
+        assert!(matches!(
+            Erc::Boolean.generate(&mut rng),
+            Gene::GeneBoolean(_)
+        ));
+        match (Erc::Tag { max_tag: 9 }).generate(&mut rng) {
+            Gene::TagExec(tag) | Gene::TagInt(tag) => assert!(tag <= 9),
+            gene => panic!("Expected a tag, got {:?}", gene),
+        }
+        assert_eq!(
+            Gene::Tagged(0),
+            Erc::Tagged { max_tag: 0 }.generate(&mut rng)
+        );
+        for _ in 0..20 {
+            match (Erc::Name { pool_size: 3 }).generate(&mut rng) {
+                Gene::Name(name) => assert!(["n0", "n1", "n2"].contains(&name.as_str())),
+                gene => panic!("Expected a name, got {:?}", gene),
+            }
+        }
+    }
+
+    #[test]
+    fn instantiate_test() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let gene = instantiate(Gene::Erc(Erc::Int { min: 5, max: 5 }), &mut rng);
+        assert_eq!(Gene::GeneInt(5), gene);
+        assert_eq!(Gene::Close, instantiate(Gene::Close, &mut rng));
+    }
+}
@@ -1,9 +1,57 @@
+use crate::instructions::utils::Overflow;
 use crate::push::state::*;
+use crate::push::tag::{tag_exec, tag_int, tagged};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Why the interpreter couldn't run a program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterpreterError {
+    /// A gene that only belongs in plushies or the gene pool, like
+    /// `Close`, reached the exec stack.
+    PlushyGene(Gene),
+    /// A `Place` asked for an input the state doesn't have.
+    MissingInput(usize),
+}
+
+impl fmt::Display for InterpreterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InterpreterError::PlushyGene(gene) => {
+                write!(f, "{:?} found in the exec stack", gene)
+            }
+            InterpreterError::MissingInput(idx) => write!(f, "No input at index {}", idx),
+        }
+    }
+}
+
+impl std::error::Error for InterpreterError {}
+
+/// Why `interpret_program` stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    /// The exec stack ran out.
+    Halted,
+    /// The step limit was reached with items left on the exec stack.
+    StepLimit,
+}
+
+/// A report of how a program ran.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionOutcome {
+    pub steps: usize,
+    pub termination: Termination,
+    /// The most items each stack held after any step, before truncation.
+    pub peak_depths: HashMap<Stack, usize>,
+    /// How many times a stack went over the max stack size and had its
+    /// oldest items removed.
+    pub truncations: usize,
+}
 
 /// The main function that disperses the exec stack Genes into
 /// the respective stacks. Also is where the individual instructions
 /// (such as int_add) is ran.
-pub fn gene_to_stack(state: &mut PushState, gene: Gene) {
+pub fn gene_to_stack(state: &mut PushState, gene: Gene) -> Result<(), InterpreterError> {
     match gene {
         Gene::GeneInt(x) => state.int.push(x),
         Gene::GeneFloat(x) => state.float.push(x),
@@ -15,21 +63,31 @@ pub fn gene_to_stack(state: &mut PushState, gene: Gene) {
         Gene::GeneVectorBoolean(x) => state.vector_boolean.push(x),
         Gene::GeneVectorString(x) => state.vector_string.push(x),
         Gene::GeneVectorChar(x) => state.vector_char.push(x),
-        Gene::StateFunc(func) => func(state),
+        Gene::StateFunc(instr) => (instr.function)(state),
         Gene::Block(x) => state.exec.extend(x.into_iter().rev()),
-        Gene::Close => panic!("Close found in the exec stack, this should not happen!"),
-        Gene::Open(_) => panic!("Open found in the exec stack, this should not happen!"),
+        Gene::Close | Gene::Open(_) | Gene::CrossoverPadding | Gene::Erc(_) => {
+            return Err(InterpreterError::PlushyGene(gene));
+        }
         Gene::Skip => {
             state.exec.pop(); // Skip the next item by removing it.
         }
-        Gene::CrossoverPadding => {
-            panic!("CrossoverPadding found in the exec stack, this should not happen!")
-        }
         Gene::Place(idx) => {
-            let var = state.input[idx].clone();
+            let var = state
+                .input
+                .get(idx)
+                .ok_or(InterpreterError::MissingInput(idx))?
+                .clone();
             state.exec.push(var)
         }
+        Gene::Name(name) => match state.bindings.get(&name) {
+            Some(val) => state.exec.push(val.clone()), // Recall the bound value.
+            None => state.name.push(name),
+        },
+        Gene::TagExec(tag) => tag_exec(state, tag),
+        Gene::TagInt(tag) => tag_int(state, tag),
+        Gene::Tagged(tag) => tagged(state, tag),
     }
+    Ok(())
 }
 
 /// Ensures the stacks don't go over a set size. Removes the oldest values first.
@@ -52,15 +110,65 @@ macro_rules! ensure_boundaries {
     };
 }
 
+/// Cuts the output down to `max_output_length` chars.
+fn ensure_output_length(state: &mut PushState, max_output_length: usize) {
+    if state.output.len() > max_output_length
+        && let Some((idx, _)) = state.output.char_indices().nth(max_output_length)
+    {
+        state.output.truncate(idx);
+    }
+}
+
+/// Records the depth of every stack after a step into `peaks`, indexed like
+/// `Stack::ALL`. Returns how many stacks went over `max_stack_size`.
+fn record_depths(
+    state: &PushState,
+    max_stack_size: usize,
+    peaks: &mut [usize; Stack::ALL.len()],
+) -> usize {
+    let mut truncations = 0;
+    for (stack, peak) in Stack::ALL.into_iter().zip(peaks.iter_mut()) {
+        if let Some(depth) = state.depth(stack) {
+            *peak = (*peak).max(depth);
+            if depth > max_stack_size {
+                truncations += 1;
+            }
+        }
+    }
+    truncations
+}
+
 /// Where a push program's exec stack is interpreted to completion.
+/// Every stack, exec and code included, is cut down to `max_stack_size`
+/// items after each step. Output past `max_output_length` chars is dropped.
+/// Instructions can't build code with more than `max_points` points, and int
+/// arithmetic follows `overflow`. Stops at the first gene that can't be
+/// run and returns why.
 /// TODO: Decide where to place loading in a push program.
-pub fn interpret_program(state: &mut PushState, step_limit: usize, max_stack_size: usize) {
-    let mut steps: usize = 0;
-    while !state.exec.is_empty() && steps < step_limit {
+pub fn interpret_program(
+    state: &mut PushState,
+    step_limit: usize,
+    max_stack_size: usize,
+    max_output_length: usize,
+    max_points: usize,
+    overflow: Overflow,
+) -> Result<ExecutionOutcome, InterpreterError> {
+    state.max_points = max_points;
+    state.max_stack_size = max_stack_size;
+    state.overflow = overflow;
+    let mut outcome = ExecutionOutcome {
+        steps: 0,
+        termination: Termination::Halted,
+        peak_depths: HashMap::new(),
+        truncations: 0,
+    };
+    let mut peaks = [0; Stack::ALL.len()];
+    while !state.exec.is_empty() && outcome.steps < step_limit {
         if let Some(gene) = state.exec.pop() {
-            gene_to_stack(state, gene);
-            steps += 1;
+            gene_to_stack(state, gene)?;
+            outcome.steps += 1;
         }
+        outcome.truncations += record_depths(state, max_stack_size, &mut peaks);
         // If adding any more stacks in the future, must be added to this list
         ensure_boundaries!(
             state,
@@ -74,9 +182,23 @@ pub fn interpret_program(state: &mut PushState, step_limit: usize, max_stack_siz
             vector_float,
             vector_string,
             vector_boolean,
-            vector_char
+            vector_char,
+            name,
+            code,
+            exec
         );
+        ensure_output_length(state, max_output_length);
     }
+    // Print has no depth
+    outcome.peak_depths = Stack::ALL
+        .into_iter()
+        .zip(peaks)
+        .filter(|(stack, _)| state.depth(*stack).is_some())
+        .collect();
+    if !state.exec.is_empty() {
+        outcome.termination = Termination::StepLimit;
+    }
+    Ok(outcome)
 }
 
 #[cfg(test)]
@@ -90,42 +212,44 @@ mod tests {
     fn gene_to_stack_test() {
         let mut test_state = EMPTY_STATE;
 
-        gene_to_stack(&mut test_state, Gene::GeneInt(1));
+        gene_to_stack(&mut test_state, Gene::GeneInt(1)).unwrap();
         assert_eq!(vec![1], test_state.int);
         test_state.int.clear();
 
-        gene_to_stack(&mut test_state, Gene::GeneFloat(dec!(1.2)));
-        gene_to_stack(&mut test_state, Gene::GeneFloat(dec!(2.4)));
+        gene_to_stack(&mut test_state, Gene::GeneFloat(dec!(1.2))).unwrap();
+        gene_to_stack(&mut test_state, Gene::GeneFloat(dec!(2.4))).unwrap();
         assert_eq!(vec![dec!(1.2), dec!(2.4)], test_state.float);
         test_state.float.clear();
 
-        gene_to_stack(&mut test_state, Gene::GeneBoolean(true));
+        gene_to_stack(&mut test_state, Gene::GeneBoolean(true)).unwrap();
         assert_eq!(vec![true], test_state.boolean);
         test_state.boolean.clear();
 
-        gene_to_stack(&mut test_state, Gene::GeneString(vec!['t', 'e', 's', 't']));
+        gene_to_stack(&mut test_state, Gene::GeneString(vec!['t', 'e', 's', 't'])).unwrap();
         assert_eq!(vec![vec!['t', 'e', 's', 't']], test_state.string);
         test_state.string.clear();
 
-        gene_to_stack(&mut test_state, Gene::GeneChar('a'));
-        gene_to_stack(&mut test_state, Gene::GeneChar('b'));
-        gene_to_stack(&mut test_state, Gene::GeneChar('c'));
+        gene_to_stack(&mut test_state, Gene::GeneChar('a')).unwrap();
+        gene_to_stack(&mut test_state, Gene::GeneChar('b')).unwrap();
+        gene_to_stack(&mut test_state, Gene::GeneChar('c')).unwrap();
         assert_eq!(vec!['a', 'b', 'c'], test_state.char);
         test_state.char.clear();
 
-        gene_to_stack(&mut test_state, Gene::GeneVectorInt(vec![1, 2, 3]));
-        gene_to_stack(&mut test_state, Gene::GeneVectorInt(vec![4, 5, 6]));
+        gene_to_stack(&mut test_state, Gene::GeneVectorInt(vec![1, 2, 3])).unwrap();
+        gene_to_stack(&mut test_state, Gene::GeneVectorInt(vec![4, 5, 6])).unwrap();
         assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6]], test_state.vector_int);
         test_state.vector_int.clear();
 
         gene_to_stack(
             &mut test_state,
             Gene::GeneVectorFloat(vec![dec!(1.7), dec!(2.4), dec!(3.9)]),
-        );
+        )
+        .unwrap();
         gene_to_stack(
             &mut test_state,
             Gene::GeneVectorFloat(vec![dec!(4.7), dec!(5.4), dec!(6.9)]),
-        );
+        )
+        .unwrap();
         assert_eq!(
             vec![
                 vec![dec!(1.7), dec!(2.4), dec!(3.9)],
@@ -135,21 +259,23 @@ mod tests {
         );
         test_state.vector_float.clear();
 
-        gene_to_stack(&mut test_state, Gene::GeneVectorBoolean(vec![true, false]));
+        gene_to_stack(&mut test_state, Gene::GeneVectorBoolean(vec![true, false])).unwrap();
         assert_eq!(vec![vec![true, false]], test_state.vector_boolean);
         test_state.vector_boolean.clear();
 
         gene_to_stack(
             &mut test_state,
             Gene::GeneVectorString(vec![vec!['t', 'e', 's', 't', '0']]),
-        );
+        )
+        .unwrap();
         gene_to_stack(
             &mut test_state,
             Gene::GeneVectorString(vec![
                 vec!['t', 'e', 's', 't', '1'],
                 vec!['t', 'e', 's', 't', '2'],
             ]),
-        );
+        )
+        .unwrap();
         assert_eq!(
             vec![
                 vec![vec!['t', 'e', 's', 't', '0']],
@@ -159,8 +285,8 @@ mod tests {
         );
         test_state.vector_string.clear();
 
-        gene_to_stack(&mut test_state, Gene::GeneVectorChar(vec!['a', 'b']));
-        gene_to_stack(&mut test_state, Gene::GeneVectorChar(vec!['b', 'c', 'd']));
+        gene_to_stack(&mut test_state, Gene::GeneVectorChar(vec!['a', 'b'])).unwrap();
+        gene_to_stack(&mut test_state, Gene::GeneVectorChar(vec!['b', 'c', 'd'])).unwrap();
         assert_eq!(
             vec![vec!['a', 'b'], vec!['b', 'c', 'd']],
             test_state.vector_char
@@ -170,14 +296,14 @@ mod tests {
         let test_block: Gene = Gene::Block(vec![
             Gene::GeneInt(1),
             Gene::GeneFloat(dec!(2.3)),
-            Gene::StateFunc(int_add),
+            Gene::StateFunc(instruction!(int_add)),
         ]);
         test_state.exec.push(Gene::GeneInt(2));
-        gene_to_stack(&mut test_state, test_block);
+        gene_to_stack(&mut test_state, test_block).unwrap();
         assert_eq!(
             vec![
                 Gene::GeneInt(2),
-                Gene::StateFunc(int_add),
+                Gene::StateFunc(instruction!(int_add)),
                 Gene::GeneFloat(dec!(2.3)),
                 Gene::GeneInt(1),
             ],
@@ -192,16 +318,179 @@ mod tests {
         let mut test_state = EMPTY_STATE;
 
         test_state.exec = vec![
-            Gene::StateFunc(int_add),
-            Gene::StateFunc(int_add),
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::StateFunc(instruction!(int_add)),
             Gene::GeneInt(2),
             Gene::GeneInt(3),
             Gene::GeneInt(4),
         ];
-        interpret_program(&mut test_state, 1000, 1000);
+        interpret_program(&mut test_state, 1000, 1000, 1000, 100, Overflow::Checked).unwrap();
         assert_eq!(vec![9], test_state.int);
     }
 
+    #[test]
+    fn overflow_policy_test() {
+        let mut test_state = EMPTY_STATE;
+
+        test_state.exec = vec![
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::GeneInt(1),
+            Gene::GeneInt(i128::MAX),
+        ];
+        interpret_program(&mut test_state, 1000, 1000, 1000, 100, Overflow::Saturating).unwrap();
+        assert_eq!(Overflow::Saturating, test_state.overflow);
+        assert_eq!(vec![i128::MAX], test_state.int);
+    }
+
+    #[test]
+    fn max_points_policy_test() {
+        use crate::instructions::code::code_wrap_block;
+
+        let mut test_state = EMPTY_STATE;
+
+        test_state.code = vec![Gene::GeneInt(1)];
+        test_state.exec = vec![Gene::StateFunc(instruction!(code_wrap_block))];
+        interpret_program(&mut test_state, 1000, 1000, 1000, 1, Overflow::Checked).unwrap();
+        assert_eq!(1, test_state.max_points);
+        assert_eq!(1000, test_state.max_stack_size);
+        assert_eq!(vec![Gene::GeneInt(1)], test_state.code);
+    }
+
+    #[test]
+    fn execution_outcome_test() {
+        use crate::instructions::code::exec_y;
+        use crate::instructions::common::int_dup;
+
+        let mut test_state = EMPTY_STATE;
+
+        test_state.exec = vec![
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::GeneInt(2),
+            Gene::GeneInt(3),
+            Gene::GeneInt(4),
+        ];
+        let outcome =
+            interpret_program(&mut test_state, 1000, 1000, 1000, 100, Overflow::Checked).unwrap();
+        assert_eq!(5, outcome.steps);
+        assert_eq!(Termination::Halted, outcome.termination);
+        assert_eq!(Some(&3), outcome.peak_depths.get(&Stack::Int));
+        assert_eq!(Some(&4), outcome.peak_depths.get(&Stack::Exec));
+        assert_eq!(Some(&0), outcome.peak_depths.get(&Stack::Float));
+        assert_eq!(None, outcome.peak_depths.get(&Stack::Print));
+        assert_eq!(0, outcome.truncations);
+
+        // Duplicates the int forever, going over the max stack size
+        // on every dup past the third.
+        test_state = EMPTY_STATE;
+        test_state.int = vec![1];
+        test_state.exec = vec![
+            Gene::StateFunc(instruction!(int_dup)),
+            Gene::StateFunc(instruction!(exec_y)),
+        ];
+        let outcome =
+            interpret_program(&mut test_state, 10, 3, 1000, 100, Overflow::Checked).unwrap();
+        assert_eq!(10, outcome.steps);
+        assert_eq!(Termination::StepLimit, outcome.termination);
+        assert_eq!(Some(&4), outcome.peak_depths.get(&Stack::Int));
+        assert_eq!(vec![1, 1, 1], test_state.int);
+        assert_eq!(1, outcome.truncations);
+    }
+
+    #[test]
+    fn exec_boundary_test() {
+        use crate::instructions::common::exec_dup_times;
+
+        let mut test_state = EMPTY_STATE;
+
+        test_state.int = vec![50];
+        test_state.exec = vec![
+            Gene::GeneInt(1),
+            Gene::StateFunc(instruction!(exec_dup_times)),
+        ];
+        let outcome =
+            interpret_program(&mut test_state, 1, 10, 1000, 100, Overflow::Checked).unwrap();
+        assert_eq!(10, test_state.exec.len());
+        assert_eq!(1, outcome.truncations);
+    }
+
+    #[test]
+    fn interpreter_error_test() {
+        let mut test_state = EMPTY_STATE;
+
+        assert_eq!(
+            Err(InterpreterError::PlushyGene(Gene::Close)),
+            gene_to_stack(&mut test_state, Gene::Close)
+        );
+
+        test_state.input = vec![Gene::GeneInt(1)];
+        test_state.exec = vec![Gene::Place(3), Gene::Place(0)];
+        assert_eq!(
+            Err(InterpreterError::MissingInput(3)),
+            interpret_program(&mut test_state, 1000, 1000, 1000, 100, Overflow::Checked)
+        );
+        assert_eq!(vec![1], test_state.int);
+    }
+
+    #[test]
+    fn name_recall_test() {
+        use crate::gp::genome::plushy_to_push;
+        use crate::instructions::common::{int_define, name_quote};
+
+        let mut test_state = EMPTY_STATE;
+
+        // Bind x to 5, rebind it to 6, then recall it.
+        test_state.exec = vec![
+            Gene::Name("x".to_string()),
+            Gene::StateFunc(instruction!(int_define)),
+            Gene::Name("x".to_string()),
+            Gene::StateFunc(instruction!(name_quote)),
+            Gene::GeneInt(6),
+            Gene::StateFunc(instruction!(int_define)),
+            Gene::Name("x".to_string()),
+            Gene::GeneInt(5),
+        ];
+        interpret_program(&mut test_state, 1000, 1000, 1000, 100, Overflow::Checked).unwrap();
+        assert_eq!(vec![6], test_state.int);
+        assert!(test_state.name.is_empty());
+        assert_eq!(Some(&Gene::GeneInt(6)), test_state.bindings.get("x"));
+
+        // name_quote opens a block in a plushy, the name is quoted from it.
+        let push_program = plushy_to_push(vec![
+            Gene::GeneInt(5),
+            Gene::StateFunc(instruction!(name_quote)),
+            Gene::Name("n0".to_string()),
+            Gene::Close,
+            Gene::StateFunc(instruction!(int_define)),
+            Gene::Name("n0".to_string()),
+        ]);
+        let mut test_state = EMPTY_STATE;
+        test_state.exec = push_program.into_iter().rev().collect();
+        interpret_program(&mut test_state, 1000, 1000, 1000, 100, Overflow::Checked).unwrap();
+        assert_eq!(vec![5], test_state.int);
+        assert_eq!(Some(&Gene::GeneInt(5)), test_state.bindings.get("n0"));
+    }
+
+    #[test]
+    fn max_output_length_test() {
+        use crate::instructions::print::{print_newline, print_string};
+
+        let mut test_state = EMPTY_STATE;
+
+        test_state.exec = vec![
+            Gene::StateFunc(instruction!(print_string)),
+            Gene::GeneString(vec!['é', 'b', 'c']),
+            Gene::StateFunc(instruction!(print_newline)),
+        ];
+        interpret_program(&mut test_state, 1000, 1000, 3, 100, Overflow::Checked).unwrap();
+        assert_eq!("\néb", test_state.output);
+
+        test_state.output.clear();
+        test_state.exec = vec![Gene::StateFunc(instruction!(print_newline))];
+        interpret_program(&mut test_state, 1000, 1000, 0, 100, Overflow::Checked).unwrap();
+        assert_eq!("", test_state.output);
+    }
+
     #[test]
     fn boundary_test() {
         let mut test_state = EMPTY_STATE;
@@ -1,3 +1,7 @@
+pub mod erc;
 pub mod interpreter;
+pub mod parser;
+pub mod serialization;
 pub mod state;
+pub mod tag;
 pub mod utils;
@@ -0,0 +1,575 @@
+//! # Program Text
+//!
+//! Reads and writes push programs as text, such as
+//! `( int_add 3 "abc" [1 2 3] ( exec_dup ( 1 ) ) in0 )`.
+//!
+//! The whole program is wrapped in parentheses and every inner pair of
+//! parentheses is a block. The other tokens are:
+//!
+//! - Instructions by name, such as `int_add`.
+//! - Ints like `-3` and floats, which always have a decimal point, like `1.0`.
+//! - `true` and `false`.
+//! - Chars like `'a'` and strings like `"abc"`, with `\n`, `\t`, `\r`, `\0`,
+//!   `\\`, `\'`, `\"`, and `\u{e9}` escapes.
+//! - Vectors like `[1 2 3]`, `[1.0 2.5]`, `[true]`, `["a" "b"]`, and
+//!   `['a' 'b']`. A type prefix like `int[]`, `float[]`, `boolean[]`,
+//!   `string[]`, or `char[]` picks the type of a vector, which empty vectors
+//!   need.
+//! - `in0`, `in1`, ... for `Gene::Place`.
+//! - `name:x` for the name `x`.
+//! - `tag_exec_N`, `tag_int_N`, and `tagged_N` for tags.
+//! - `close`, `skip`, `open1`, `open2`, ..., and `crossover_padding` for the
+//!   plushy only genes.
+//!
+//! ERC genes only live in the gene pool, so they are written by kind alone,
+//! like `erc_int`, and can't be read back.
+
+use crate::instructions::registry::INSTRUCTION_REGISTRY;
+use crate::push::state::Gene;
+use rust_decimal::Decimal;
+use std::fmt;
+use std::str::FromStr;
+
+/// Why a program failed to parse. `position` is the char index the error was found at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub position: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a program written like `( int_add 3 ( exec_dup ( 1 ) ) )`.
+pub fn parse_program(text: &str) -> Result<Vec<Gene>, ParseError> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        pos: 0,
+    };
+    parser.skip_whitespace();
+    parser.expect('(')?;
+    let genes = parser.parse_block()?;
+    parser.skip_whitespace();
+    if parser.pos < parser.chars.len() {
+        return Err(parser.error("Unexpected text after the program"));
+    }
+    Ok(genes)
+}
+
+/// Writes a program in the syntax `parse_program` reads.
+pub fn program_to_string(genes: &[Gene]) -> String {
+    let mut text = "(".to_string();
+    for gene in genes {
+        text.push(' ');
+        text.push_str(&gene_to_string(gene));
+    }
+    text.push_str(" )");
+    text
+}
+
+/// Writes a single gene in the syntax `parse_program` reads.
+pub fn gene_to_string(gene: &Gene) -> String {
+    match gene {
+        Gene::GeneInt(val) => val.to_string(),
+        Gene::GeneFloat(val) => float_to_string(val),
+        Gene::GeneBoolean(val) => val.to_string(),
+        Gene::GeneString(val) => string_to_string(val),
+        Gene::GeneChar(val) => char_to_string(*val),
+        Gene::GeneVectorInt(val) => vector_to_string("int", val, |el| el.to_string()),
+        Gene::GeneVectorFloat(val) => vector_to_string("float", val, float_to_string),
+        Gene::GeneVectorBoolean(val) => vector_to_string("boolean", val, |el| el.to_string()),
+        Gene::GeneVectorString(val) => vector_to_string("string", val, |el| string_to_string(el)),
+        Gene::GeneVectorChar(val) => vector_to_string("char", val, |el| char_to_string(*el)),
+        Gene::StateFunc(instr) => instr.name.to_string(),
+        Gene::Close => "close".to_string(),
+        Gene::Open(val) => format!("open{}", val),
+        Gene::Skip => "skip".to_string(),
+        Gene::Block(block) => program_to_string(block),
+        Gene::CrossoverPadding => "crossover_padding".to_string(),
+        Gene::Place(val) => format!("in{}", val),
+        Gene::Erc(erc) => erc.name().to_string(),
+        Gene::Name(name) => format!("name:{}", name),
+        Gene::TagExec(tag) => format!("tag_exec_{}", tag),
+        Gene::TagInt(tag) => format!("tag_int_{}", tag),
+        Gene::Tagged(tag) => format!("tagged_{}", tag),
+    }
+}
+
+fn float_to_string(val: &Decimal) -> String {
+    let text = val.to_string();
+    if text.contains('.') {
+        text
+    } else {
+        format!("{}.0", text)
+    }
+}
+
+fn escape_char(c: char, quote: char) -> String {
+    match c {
+        '\n' => "\\n".to_string(),
+        '\t' => "\\t".to_string(),
+        '\r' => "\\r".to_string(),
+        '\0' => "\\0".to_string(),
+        '\\' => "\\\\".to_string(),
+        c if c == quote => format!("\\{}", c),
+        c if c.is_control() => format!("\\u{{{:x}}}", c as u32),
+        c => c.to_string(),
+    }
+}
+
+fn char_to_string(c: char) -> String {
+    format!("'{}'", escape_char(c, '\''))
+}
+
+fn string_to_string(chars: &[char]) -> String {
+    let inner: String = chars.iter().map(|c| escape_char(*c, '"')).collect();
+    format!("\"{}\"", inner)
+}
+
+fn vector_to_string<T>(prefix: &str, vals: &[T], el_to_string: impl Fn(&T) -> String) -> String {
+    if vals.is_empty() {
+        return format!("{}[]", prefix);
+    }
+    let inner: Vec<String> = vals.iter().map(el_to_string).collect();
+    format!("[{}]", inner.join(" "))
+}
+
+/// A vector element before the vector's type is known.
+enum Element {
+    Atom(String),
+    String(Vec<char>),
+    Char(char),
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            message: message.into(),
+            position: self.pos,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.pos += 1;
+        c
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            _ => Err(self.error(format!("Expected '{}'", expected))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Parses the genes of a block whose opening parenthesis was already read.
+    fn parse_block(&mut self) -> Result<Vec<Gene>, ParseError> {
+        let mut genes = vec![];
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None => return Err(self.error("Missing ')'")),
+                Some(')') => {
+                    self.pos += 1;
+                    return Ok(genes);
+                }
+                Some('(') => {
+                    self.pos += 1;
+                    genes.push(Gene::Block(self.parse_block()?));
+                }
+                Some('"') => genes.push(Gene::GeneString(self.parse_quoted('"')?)),
+                Some('\'') => genes.push(Gene::GeneChar(self.parse_char()?)),
+                Some('[') => genes.push(self.parse_vector(None)?),
+                Some(_) => {
+                    let start = self.pos;
+                    let atom = self.parse_atom();
+                    if self.peek() == Some('[') {
+                        genes.push(self.parse_vector(Some(&atom))?);
+                    } else {
+                        genes.push(self.atom_to_gene(&atom, start)?);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reads a bare token, ending at whitespace or punctuation.
+    fn parse_atom(&mut self) -> String {
+        let mut atom = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || "()[]\"'".contains(c) {
+                break;
+            }
+            atom.push(c);
+            self.pos += 1;
+        }
+        atom
+    }
+
+    fn atom_to_gene(&self, atom: &str, start: usize) -> Result<Gene, ParseError> {
+        let number_suffix = |prefix: &str| {
+            atom.strip_prefix(prefix)
+                .filter(|rest| !rest.is_empty() && rest.chars().all(|c| c.is_ascii_digit()))
+        };
+        let gene = match atom {
+            "true" => Gene::GeneBoolean(true),
+            "false" => Gene::GeneBoolean(false),
+            "close" => Gene::Close,
+            "skip" => Gene::Skip,
+            "crossover_padding" => Gene::CrossoverPadding,
+            _ => {
+                if let Some(val) = number_suffix("in") {
+                    Gene::Place(
+                        val.parse()
+                            .map_err(|_| self.error_at("Invalid input", start))?,
+                    )
+                } else if let Some(val) = number_suffix("open") {
+                    Gene::Open(
+                        val.parse()
+                            .map_err(|_| self.error_at("Invalid open", start))?,
+                    )
+                } else if let Some(val) = number_suffix("tag_exec_") {
+                    Gene::TagExec(
+                        val.parse()
+                            .map_err(|_| self.error_at("Invalid tag", start))?,
+                    )
+                } else if let Some(val) = number_suffix("tag_int_") {
+                    Gene::TagInt(
+                        val.parse()
+                            .map_err(|_| self.error_at("Invalid tag", start))?,
+                    )
+                } else if let Some(val) = number_suffix("tagged_") {
+                    Gene::Tagged(
+                        val.parse()
+                            .map_err(|_| self.error_at("Invalid tag", start))?,
+                    )
+                } else if let Some(name) = atom.strip_prefix("name:").filter(|n| !n.is_empty()) {
+                    Gene::Name(name.to_string())
+                } else if let Ok(val) = atom.parse::<i128>() {
+                    Gene::GeneInt(val)
+                } else if let Some(val) = parse_float(atom) {
+                    Gene::GeneFloat(val)
+                } else if let Some(info) = INSTRUCTION_REGISTRY.get(atom) {
+                    Gene::StateFunc(info.instruction())
+                } else {
+                    return Err(self.error_at(format!("Unknown token '{}'", atom), start));
+                }
+            }
+        };
+        Ok(gene)
+    }
+
+    fn error_at(&self, message: impl Into<String>, position: usize) -> ParseError {
+        ParseError {
+            message: message.into(),
+            position,
+        }
+    }
+
+    fn parse_char(&mut self) -> Result<char, ParseError> {
+        let start = self.pos;
+        let chars = self.parse_quoted('\'')?;
+        if chars.len() != 1 {
+            return Err(self.error_at("A char must hold exactly one character", start));
+        }
+        Ok(chars[0])
+    }
+
+    /// Reads a quoted string or char, handling escapes.
+    fn parse_quoted(&mut self, quote: char) -> Result<Vec<char>, ParseError> {
+        self.expect(quote)?;
+        let mut chars = vec![];
+        loop {
+            match self.next() {
+                None => return Err(self.error(format!("Missing closing {}", quote))),
+                Some(c) if c == quote => return Ok(chars),
+                Some('\\') => chars.push(self.parse_escape()?),
+                Some(c) => chars.push(c),
+            }
+        }
+    }
+
+    fn parse_escape(&mut self) -> Result<char, ParseError> {
+        let escaped = match self.next() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('0') => '\0',
+            Some('\\') => '\\',
+            Some('\'') => '\'',
+            Some('"') => '"',
+            Some('u') => {
+                self.expect('{')?;
+                let mut hex = String::new();
+                while let Some(c) = self.next() {
+                    if c == '}' {
+                        break;
+                    }
+                    hex.push(c);
+                }
+                u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.error(format!("Invalid unicode escape '{}'", hex)))?
+            }
+            _ => return Err(self.error("Unknown escape")),
+        };
+        Ok(escaped)
+    }
+
+    /// Parses a vector. `prefix` is the type written before the `[`, if any.
+    fn parse_vector(&mut self, prefix: Option<&str>) -> Result<Gene, ParseError> {
+        let start = self.pos;
+        self.expect('[')?;
+        let mut elements = vec![];
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None => return Err(self.error("Missing ']'")),
+                Some(']') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('"') => elements.push(Element::String(self.parse_quoted('"')?)),
+                Some('\'') => elements.push(Element::Char(self.parse_char()?)),
+                Some(_) => {
+                    let atom = self.parse_atom();
+                    if atom.is_empty() {
+                        return Err(self.error("Unexpected character in vector"));
+                    }
+                    elements.push(Element::Atom(atom));
+                }
+            }
+        }
+
+        let kind = match prefix {
+            Some(prefix) => prefix,
+            None => match elements.first() {
+                None => {
+                    return Err(self.error_at("An empty vector needs a type, like int[]", start));
+                }
+                Some(Element::String(_)) => "string",
+                Some(Element::Char(_)) => "char",
+                Some(Element::Atom(atom)) if atom == "true" || atom == "false" => "boolean",
+                Some(Element::Atom(_)) => {
+                    let has_float = elements
+                        .iter()
+                        .any(|el| matches!(el, Element::Atom(atom) if atom.contains('.')));
+                    if has_float { "float" } else { "int" }
+                }
+            },
+        };
+
+        let wrong_type = || self.error_at(format!("Vector elements must be {}s", kind), start);
+        let atoms = || -> Result<Vec<&String>, ParseError> {
+            elements
+                .iter()
+                .map(|el| match el {
+                    Element::Atom(atom) => Ok(atom),
+                    _ => Err(wrong_type()),
+                })
+                .collect()
+        };
+        let gene = match kind {
+            "int" => Gene::GeneVectorInt(
+                atoms()?
+                    .into_iter()
+                    .map(|atom| atom.parse().map_err(|_| wrong_type()))
+                    .collect::<Result<_, _>>()?,
+            ),
+            "float" => Gene::GeneVectorFloat(
+                atoms()?
+                    .into_iter()
+                    .map(|atom| Decimal::from_str(atom).map_err(|_| wrong_type()))
+                    .collect::<Result<_, _>>()?,
+            ),
+            "boolean" => Gene::GeneVectorBoolean(
+                atoms()?
+                    .into_iter()
+                    .map(|atom| atom.parse().map_err(|_| wrong_type()))
+                    .collect::<Result<_, _>>()?,
+            ),
+            "string" => Gene::GeneVectorString(
+                elements
+                    .into_iter()
+                    .map(|el| match el {
+                        Element::String(chars) => Ok(chars),
+                        _ => Err(wrong_type()),
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            "char" => Gene::GeneVectorChar(
+                elements
+                    .into_iter()
+                    .map(|el| match el {
+                        Element::Char(c) => Ok(c),
+                        _ => Err(wrong_type()),
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => return Err(self.error_at(format!("Unknown vector type '{}'", kind), start)),
+        };
+        Ok(gene)
+    }
+}
+
+/// Floats must have a decimal point so they aren't confused with ints.
+fn parse_float(atom: &str) -> Option<Decimal> {
+    if atom.contains('.') {
+        Decimal::from_str(atom).ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::common::*;
+    use crate::instructions::numeric::*;
+    use crate::push::utils::most_genes;
+    use rust_decimal::dec;
+
+    #[test]
+    fn parse_program_test() {
+        let genes = parse_program("( int_add 3 \"abc\" [1 2 3] ( exec_dup ( 1 ) ) in0 )").unwrap();
+        assert_eq!(
+            vec![
+                Gene::StateFunc(instruction!(int_add)),
+                Gene::GeneInt(3),
+                Gene::GeneString(vec!['a', 'b', 'c']),
+                Gene::GeneVectorInt(vec![1, 2, 3]),
+                Gene::Block(vec![
+                    Gene::StateFunc(instruction!(exec_dup)),
+                    Gene::Block(vec![Gene::GeneInt(1)]),
+                ]),
+                Gene::Place(0),
+            ],
+            genes
+        );
+
+        assert_eq!(
+            vec![
+                Gene::GeneFloat(dec!(-1.5)),
+                Gene::GeneVectorFloat(vec![dec!(1), dec!(2.5)]),
+                Gene::GeneVectorFloat(vec![dec!(1), dec!(2)]),
+                Gene::GeneVectorInt(vec![]),
+                Gene::GeneChar('\n'),
+                Gene::GeneVectorChar(vec!['a', '\'']),
+                Gene::GeneVectorString(vec![vec![], vec!['"']]),
+                Gene::GeneBoolean(false),
+                Gene::GeneVectorBoolean(vec![true]),
+            ],
+            parse_program(
+                "(-1.5 [1 2.5] float[1 2] int[] '\\n' ['a' '\\''] [\"\" \"\\\"\"] false [true])"
+            )
+            .unwrap()
+        );
+        assert_eq!(Vec::<Gene>::new(), parse_program("  ( )  ").unwrap());
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let mut genes = vec![
+            Gene::GeneInt(-3),
+            Gene::GeneInt(i128::MAX),
+            Gene::GeneFloat(dec!(2)),
+            Gene::GeneFloat(dec!(-0.125)),
+            Gene::GeneBoolean(true),
+            Gene::GeneString(vec!['a', ' ', '\t', '\\', '"', '\'', 'é', '\u{7}']),
+            Gene::GeneString(vec![]),
+            Gene::GeneChar('('),
+            Gene::GeneChar('\''),
+            Gene::GeneVectorInt(vec![1, -2]),
+            Gene::GeneVectorInt(vec![]),
+            Gene::GeneVectorFloat(vec![dec!(1), dec!(0.5)]),
+            Gene::GeneVectorFloat(vec![]),
+            Gene::GeneVectorBoolean(vec![false, true]),
+            Gene::GeneVectorBoolean(vec![]),
+            Gene::GeneVectorString(vec![vec!['h', 'i'], vec![], vec![']']]),
+            Gene::GeneVectorString(vec![]),
+            Gene::GeneVectorChar(vec!['x', ' ']),
+            Gene::GeneVectorChar(vec![]),
+            Gene::StateFunc(instruction!(code_from_exec)),
+            Gene::Close,
+            Gene::Open(2),
+            Gene::Skip,
+            Gene::Block(vec![]),
+            Gene::Block(vec![Gene::GeneInt(1), Gene::Block(vec![Gene::Close])]),
+            Gene::CrossoverPadding,
+            Gene::Place(12),
+            Gene::Name("x_1".to_string()),
+            Gene::TagExec(0),
+            Gene::TagInt(12),
+            Gene::Tagged(u32::MAX),
+        ];
+        genes.extend(most_genes());
+
+        let text = program_to_string(&genes);
+        assert_eq!(genes, parse_program(&text).unwrap());
+        assert_eq!(text, program_to_string(&parse_program(&text).unwrap()));
+    }
+
+    #[test]
+    fn program_to_string_test() {
+        assert_eq!(
+            "( int_add 3 \"abc\" [1 2 3] ( exec_dup ( 1 ) ) in0 )",
+            program_to_string(&[
+                Gene::StateFunc(instruction!(int_add)),
+                Gene::GeneInt(3),
+                Gene::GeneString(vec!['a', 'b', 'c']),
+                Gene::GeneVectorInt(vec![1, 2, 3]),
+                Gene::Block(vec![
+                    Gene::StateFunc(instruction!(exec_dup)),
+                    Gene::Block(vec![Gene::GeneInt(1)]),
+                ]),
+                Gene::Place(0),
+            ])
+        );
+        assert_eq!(
+            "( 2.0 float[] )",
+            program_to_string(&[Gene::GeneFloat(dec!(2)), Gene::GeneVectorFloat(vec![]),])
+        );
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let err = parse_program("( int_add int_not_real )").unwrap_err();
+        assert_eq!("Unknown token 'int_not_real'", err.message);
+        assert_eq!(10, err.position);
+
+        assert!(parse_program("int_add").is_err());
+        assert!(parse_program("( int_add").is_err());
+        assert!(parse_program("( int_add ) )").is_err());
+        assert!(parse_program("( [] )").is_err());
+        assert!(parse_program("( [1 \"a\"] )").is_err());
+        assert!(parse_program("( 'ab' )").is_err());
+        assert!(parse_program("( \"abc )").is_err());
+        assert!(parse_program("( number[1] )").is_err());
+        assert!(parse_program("( name: )").is_err());
+        assert!(parse_program("( tagged_99999999999 )").is_err());
+    }
+}
@@ -0,0 +1,178 @@
+//! # Serialization
+//!
+//! `Gene` and `PushState` (and `Individual` in the gp module) implement
+//! serde's `Serialize` and `Deserialize`. Instruction genes are written as
+//! their instruction name, so saved programs stay valid across rebuilds.
+//! Reading an instruction name that isn't registered fails with an
+//! `Unknown instruction` error.
+//!
+//! JSON is readable, the binary format (bincode) is compact.
+
+use crate::instructions::registry::INSTRUCTION_REGISTRY;
+use crate::push::state::Instruction;
+use serde::de::{DeserializeOwned, Error as DeError};
+use serde::ser::Error as SerError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serializes `Gene::StateFunc` by instruction name.
+/// Use with `#[serde(with = "instruction_name")]`.
+pub mod instruction_name {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(instr: &Instruction, serializer: S) -> Result<S::Ok, S::Error> {
+        match INSTRUCTION_REGISTRY.get(instr.name) {
+            Some(_) => serializer.serialize_str(instr.name),
+            None => Err(S::Error::custom(
+                "Instruction missing from INSTRUCTION_REGISTRY",
+            )),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Instruction, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        INSTRUCTION_REGISTRY
+            .get(&name)
+            .map(|info| info.instruction())
+            .ok_or_else(|| D::Error::custom(format!("Unknown instruction: {}", name)))
+    }
+}
+
+/// Serializes a value to JSON.
+pub fn to_json<T: Serialize>(value: &T) -> serde_json::Result<String> {
+    serde_json::to_string(value)
+}
+
+/// Deserializes a value from JSON.
+pub fn from_json<T: DeserializeOwned>(text: &str) -> serde_json::Result<T> {
+    serde_json::from_str(text)
+}
+
+/// Serializes a value to the compact binary format.
+pub fn to_binary<T: Serialize>(value: &T) -> bincode::Result<Vec<u8>> {
+    bincode::serialize(value)
+}
+
+/// Deserializes a value from the compact binary format.
+pub fn from_binary<T: DeserializeOwned>(bytes: &[u8]) -> bincode::Result<T> {
+    bincode::deserialize(bytes)
+}
+
+/// One or more genes of every kind, including every registered instruction.
+/// Shared by the serialization and checkpoint tests.
+#[cfg(test)]
+pub(crate) fn all_gene_kinds() -> Vec<crate::push::state::Gene> {
+    use crate::instructions::numeric::*;
+    use crate::push::erc::Erc;
+    use crate::push::state::Gene;
+    use crate::push::utils::most_genes;
+    use rust_decimal::dec;
+
+    let mut genes = vec![
+        Gene::GeneInt(i128::MIN),
+        Gene::GeneFloat(dec!(-1.250)),
+        Gene::GeneBoolean(true),
+        Gene::GeneString(vec!['a', '"', '\n']),
+        Gene::GeneString(vec![]),
+        Gene::GeneChar('é'),
+        Gene::GeneVectorInt(vec![1, 2]),
+        Gene::GeneVectorFloat(vec![dec!(0.5)]),
+        Gene::GeneVectorBoolean(vec![false]),
+        Gene::GeneVectorString(vec![vec!['h', 'i'], vec![]]),
+        Gene::GeneVectorChar(vec![]),
+        Gene::Close,
+        Gene::Open(2),
+        Gene::Skip,
+        Gene::Block(vec![
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::Block(vec![]),
+        ]),
+        Gene::CrossoverPadding,
+        Gene::Place(3),
+        Gene::Erc(Erc::Float {
+            min: dec!(-1.0),
+            max: dec!(1.0),
+            places: 2,
+        }),
+        Gene::Erc(Erc::String {
+            alphabet: vec!['a', 'b'],
+            max_len: 3,
+        }),
+        Gene::Erc(Erc::Name { pool_size: 4 }),
+        Gene::Name("x".to_string()),
+        Gene::Name("a name (with) spaces".to_string()),
+        Gene::TagExec(3),
+        Gene::TagInt(0),
+        Gene::Tagged(7),
+    ];
+    genes.extend(most_genes());
+    genes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::numeric::*;
+    use crate::push::state::{EMPTY_STATE, Gene, PushState};
+    use rust_decimal::dec;
+
+    #[test]
+    fn gene_round_trip_test() {
+        let genes = all_gene_kinds();
+        assert_eq!(
+            genes,
+            from_json::<Vec<Gene>>(&to_json(&genes).unwrap()).unwrap()
+        );
+        assert_eq!(
+            genes,
+            from_binary::<Vec<Gene>>(&to_binary(&genes).unwrap()).unwrap()
+        );
+        // Scale is kept.
+        assert_eq!(
+            "[{\"StateFunc\":\"int_add\"},{\"GeneFloat\":\"1.50\"}]",
+            to_json(&vec![
+                Gene::StateFunc(instruction!(int_add)),
+                Gene::GeneFloat(dec!(1.50))
+            ])
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn state_round_trip_test() {
+        let mut state = EMPTY_STATE;
+        state.int = vec![1, -2];
+        state.float = vec![dec!(3.5)];
+        state.vector_string = vec![vec![vec!['a']]];
+        state.exec = all_gene_kinds();
+        state.code = vec![Gene::StateFunc(instruction!(float_sub))];
+        state.input = vec![Gene::GeneInt(5)];
+
+        assert_eq!(
+            state,
+            from_json::<PushState>(&to_json(&state).unwrap()).unwrap()
+        );
+        assert_eq!(
+            state,
+            from_binary::<PushState>(&to_binary(&state).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn unknown_instruction_test() {
+        let err = from_json::<Gene>("{\"StateFunc\":\"int_not_real\"}").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Unknown instruction: int_not_real")
+        );
+
+        let bytes = to_binary(&Gene::StateFunc(instruction!(int_add)))
+            .unwrap()
+            .iter()
+            .map(|b| if *b == b'a' { b'z' } else { *b })
+            .collect::<Vec<u8>>();
+        let err = from_binary::<Gene>(&bytes).unwrap_err();
+        assert!(err.to_string().contains("Unknown instruction: int_zdd"));
+    }
+}
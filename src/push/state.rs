@@ -1,12 +1,19 @@
+use crate::instructions::utils::Overflow;
+use crate::push::erc::Erc;
+use crate::push::serialization::instruction_name;
 use pyo3::FromPyObject;
 use pyo3::prelude::*;
 use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
 /// The declaration of the state that push operates on.
 ///
 /// I chose to use `rust_decimal` crate here because
 /// there are round off errors with the build in `f64`.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PushState {
     pub int: Vec<i128>,
     pub float: Vec<Decimal>,
@@ -18,9 +25,16 @@ pub struct PushState {
     pub vector_string: Vec<Vec<Vec<char>>>,
     pub vector_boolean: Vec<Vec<bool>>,
     pub vector_char: Vec<Vec<char>>,
+    pub name: Vec<String>,
     pub exec: Vec<Gene>,
     pub code: Vec<Gene>,
     pub input: Vec<Gene>,
+    pub bindings: BTreeMap<String, Gene>,
+    pub tags: BTreeMap<u32, Gene>,
+    pub output: String,
+    pub overflow: Overflow,
+    pub max_points: usize,
+    pub max_stack_size: usize,
 }
 
 pub const EMPTY_STATE: PushState = PushState {
@@ -34,12 +48,133 @@ pub const EMPTY_STATE: PushState = PushState {
     vector_string: vec![],
     vector_boolean: vec![],
     vector_char: vec![],
+    name: vec![],
     exec: vec![],
     code: vec![],
     input: vec![],
+    bindings: BTreeMap::new(),
+    tags: BTreeMap::new(),
+    output: String::new(),
+    overflow: Overflow::Checked,
+    max_points: 100,
+    max_stack_size: 100,
 };
 
-#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+impl PushState {
+    /// The number of items on a stack. `None` for `Stack::Print`, which
+    /// writes to `output` rather than holding items.
+    pub fn depth(&self, stack: Stack) -> Option<usize> {
+        let depth = match stack {
+            Stack::Int => self.int.len(),
+            Stack::Float => self.float.len(),
+            Stack::String => self.string.len(),
+            Stack::Boolean => self.boolean.len(),
+            Stack::Char => self.char.len(),
+            Stack::VectorInt => self.vector_int.len(),
+            Stack::VectorFloat => self.vector_float.len(),
+            Stack::VectorString => self.vector_string.len(),
+            Stack::VectorBoolean => self.vector_boolean.len(),
+            Stack::VectorChar => self.vector_char.len(),
+            Stack::Name => self.name.len(),
+            Stack::Code => self.code.len(),
+            Stack::Exec => self.exec.len(),
+            Stack::Print => return None,
+        };
+        Some(depth)
+    }
+}
+
+/// The stacks of a `PushState` that instructions operate on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stack {
+    Int,
+    Float,
+    String,
+    Boolean,
+    Char,
+    VectorInt,
+    VectorFloat,
+    VectorString,
+    VectorBoolean,
+    VectorChar,
+    Name,
+    Code,
+    Exec,
+    Print,
+}
+
+impl Stack {
+    /// Every stack instructions operate on.
+    pub const ALL: [Stack; 14] = [
+        Stack::Int,
+        Stack::Float,
+        Stack::String,
+        Stack::Boolean,
+        Stack::Char,
+        Stack::VectorInt,
+        Stack::VectorFloat,
+        Stack::VectorString,
+        Stack::VectorBoolean,
+        Stack::VectorChar,
+        Stack::Name,
+        Stack::Code,
+        Stack::Exec,
+        Stack::Print,
+    ];
+
+    /// The name of the stack as used in `PushState` and as the prefix of
+    /// instruction names. `Print` is the exception, its instructions write
+    /// to `PushState::output`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stack::Int => "int",
+            Stack::Float => "float",
+            Stack::String => "string",
+            Stack::Boolean => "boolean",
+            Stack::Char => "char",
+            Stack::VectorInt => "vector_int",
+            Stack::VectorFloat => "vector_float",
+            Stack::VectorString => "vector_string",
+            Stack::VectorBoolean => "vector_boolean",
+            Stack::VectorChar => "vector_char",
+            Stack::Name => "name",
+            Stack::Code => "code",
+            Stack::Exec => "exec",
+            Stack::Print => "print",
+        }
+    }
+}
+
+/// An instruction in a gene: its function along with its registered name.
+/// Instructions compare and hash by name, since two functions with the same
+/// body can share an address. Make one with `instruction!`.
+#[derive(Clone, Copy)]
+pub struct Instruction {
+    pub name: &'static str,
+    pub function: fn(&mut PushState),
+}
+
+impl PartialEq for Instruction {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Instruction {}
+
+impl Hash for Instruction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl fmt::Debug for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Hash, Serialize, Deserialize)]
 #[allow(dead_code)] // I do use these in code base. Rust doesn't see it tho :(
 pub enum Gene {
     GeneInt(i128),
@@ -52,13 +187,18 @@ pub enum Gene {
     GeneVectorBoolean(Vec<bool>),
     GeneVectorString(Vec<Vec<char>>),
     GeneVectorChar(Vec<char>),
-    StateFunc(fn(&mut PushState)),
+    StateFunc(#[serde(with = "instruction_name")] Instruction),
     Close,
     Open(u8),
     Skip,
     Block(Vec<Gene>),
     CrossoverPadding,
     Place(usize),
+    Erc(Erc),
+    Name(String),
+    TagExec(u32),
+    TagInt(u32),
+    Tagged(u32),
 }
 
 impl Gene {
@@ -80,6 +220,15 @@ impl Gene {
         size
     }
 
+    /// The number of points in a gene. A block is one point plus the points
+    /// of everything inside it, any other gene is one point.
+    pub fn points(&self) -> usize {
+        match self {
+            block @ Gene::Block(_) => block.rec_len() + 1,
+            _ => 1,
+        }
+    }
+
     /// Extracts code at a point in the genome. Recurses into a block
     /// if necessary. Point is based on an int. Pulled straight from HushGP.
     pub fn code_at_point(self, index: usize) -> Option<Gene> {
@@ -189,6 +338,17 @@ mod tests {
         assert_eq!(0, block.rec_len());
     }
 
+    #[test]
+    fn points_test() {
+        assert_eq!(1, Gene::GeneInt(1).points());
+        assert_eq!(1, Gene::Block(vec![]).points());
+        let block = Gene::Block(vec![
+            Gene::GeneInt(1),
+            Gene::Block(vec![Gene::GeneInt(1), Gene::GeneInt(1)]),
+        ]);
+        assert_eq!(5, block.points());
+    }
+
     #[test]
     fn insert_test() {
         let mut block = Gene::Block(vec![
@@ -0,0 +1,87 @@
+//! # Tags
+//!
+//! Tagging from Spector's tag-based modularity. `tag_exec_N` and `tag_int_N`
+//! pop the top item of their stack and store it under the tag `N`.
+//! `tagged_N` pushes the item stored under the closest matching tag onto the
+//! exec stack: the smallest tag that is at least `N`, wrapping around to the
+//! smallest tag when every tag is below `N`. A tag never has to match
+//! exactly, so evolution can use tagged items as subroutines.
+
+use crate::push::state::{Gene, PushState};
+use std::collections::BTreeMap;
+
+/// The item stored under the tag closest to `tag`, if anything is tagged.
+pub fn closest_tagged(tags: &BTreeMap<u32, Gene>, tag: u32) -> Option<&Gene> {
+    tags.range(tag..)
+        .next()
+        .or_else(|| tags.iter().next())
+        .map(|(_, gene)| gene)
+}
+
+/// Blocks `tag_exec_N` opens in a plushy, one for the exec item it tags. This
+/// is the `blocks_opened` of a registered instruction, for the tag gene.
+pub const TAG_EXEC_BLOCKS_OPENED: u8 = 1;
+
+/// Tags the top exec item. This is `tag_exec_N`.
+pub fn tag_exec(state: &mut PushState, tag: u32) {
+    if let Some(gene) = state.exec.pop() {
+        state.tags.insert(tag, gene);
+    }
+}
+
+/// Tags the top int. This is `tag_int_N`.
+pub fn tag_int(state: &mut PushState, tag: u32) {
+    if let Some(val) = state.int.pop() {
+        state.tags.insert(tag, Gene::GeneInt(val));
+    }
+}
+
+/// Pushes the closest tagged item onto the exec stack. This is `tagged_N`.
+pub fn tagged(state: &mut PushState, tag: u32) {
+    if let Some(gene) = closest_tagged(&state.tags, tag) {
+        state.exec.push(gene.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::push::state::EMPTY_STATE;
+
+    #[test]
+    fn closest_tagged_test() {
+        let mut tags = BTreeMap::new();
+        assert_eq!(None, closest_tagged(&tags, 3));
+
+        tags.insert(10, Gene::GeneInt(1));
+        tags.insert(20, Gene::GeneInt(2));
+        assert_eq!(Some(&Gene::GeneInt(1)), closest_tagged(&tags, 0));
+        assert_eq!(Some(&Gene::GeneInt(1)), closest_tagged(&tags, 10));
+        assert_eq!(Some(&Gene::GeneInt(2)), closest_tagged(&tags, 11));
+        // Wraps around past the largest tag
+        assert_eq!(Some(&Gene::GeneInt(1)), closest_tagged(&tags, 21));
+    }
+
+    #[test]
+    fn tag_test() {
+        let mut test_state = EMPTY_STATE;
+
+        test_state.exec = vec![Gene::Block(vec![Gene::GeneInt(1)])];
+        tag_exec(&mut test_state, 5);
+        test_state.int = vec![7];
+        tag_int(&mut test_state, 9);
+        assert!(test_state.exec.is_empty());
+        assert!(test_state.int.is_empty());
+
+        tagged(&mut test_state, 6);
+        tagged(&mut test_state, 2);
+        assert_eq!(
+            vec![Gene::GeneInt(7), Gene::Block(vec![Gene::GeneInt(1)])],
+            test_state.exec
+        );
+
+        // Nothing to tag
+        tag_int(&mut test_state, 0);
+        assert_eq!(2, test_state.tags.len());
+    }
+}
@@ -1,10 +1,11 @@
-use crate::instructions::list::all_instructions;
+use crate::instructions::registry::INSTRUCTION_REGISTRY;
 use crate::push::state::Gene;
 
 pub fn most_genes() -> Vec<Gene> {
-    let mut instructions: Vec<Gene> = all_instructions()
-        .into_iter()
-        .map(|x| Gene::StateFunc(x))
+    let mut instructions: Vec<Gene> = INSTRUCTION_REGISTRY
+        .all()
+        .iter()
+        .map(|info| Gene::StateFunc(info.instruction()))
         .collect();
     instructions.push(Gene::Close);
     instructions.push(Gene::Skip);
@@ -1,4 +1,5 @@
 use rush::gp::individual::Individual;
+use rush::instruction;
 use rush::instructions::code::{code_but_last, code_combine};
 use rush::push::state::Gene;
 
@@ -7,8 +8,11 @@ fn run_display_test() {
     let ind: Individual = Individual {
         plushy: vec![
             Gene::GeneInt(1),
-            Gene::StateFunc(code_but_last),
-            Gene::Block(vec![Gene::GeneBoolean(true), Gene::StateFunc(code_combine)]),
+            Gene::StateFunc(instruction!(code_but_last)),
+            Gene::Block(vec![
+                Gene::GeneBoolean(true),
+                Gene::StateFunc(instruction!(code_combine)),
+            ]),
             Gene::GeneInt(9),
         ],
         push_program: None,
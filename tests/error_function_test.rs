@@ -2,12 +2,13 @@ use polars::prelude::*;
 use rand::SeedableRng;
 use rand::rngs::StdRng;
 use rush::gp::args::SearchDirection;
-use rush::gp::individual::Individual;
+use rush::gp::individual::{Individual, evaluate};
 use rush::gp::selection::{Selection, select_parent};
 use rush::gp::simplification::auto_simplify_plushy;
 use rush::gp::utils::polars_to_gene;
+use rush::instruction;
 use rush::instructions::numeric::*;
-use rush::push::interpreter::interpret_program;
+use rush::push::interpreter::{InterpreterError, interpret_program};
 use rush::push::state::Gene;
 use rush::push::utils::most_genes;
 use rush::{gp::args::PushArgs, push::state::EMPTY_STATE};
@@ -21,7 +22,7 @@ fn test_error_function(
     push_args: &PushArgs,
     data: &DataFrame,
     push_program: Vec<Gene>,
-) -> Vec<Decimal> {
+) -> Result<Vec<Decimal>, InterpreterError> {
     let mut error_vec: Vec<Decimal> = vec![];
 
     let y = data
@@ -44,7 +45,14 @@ fn test_error_function(
         }
         state.exec.extend(push_program.clone().into_iter()); // load the program
         state.input.extend(inputs.clone().into_iter()); // Make inputs available to the state
-        interpret_program(&mut state, push_args.step_limit, push_args.max_stack_size);
+        interpret_program(
+            &mut state,
+            push_args.step_limit,
+            push_args.max_stack_size,
+            push_args.max_output_length,
+            push_args.max_points,
+            push_args.overflow,
+        )?;
         if let Some(top_int) = state.int.pop() {
             error_vec.push(Decimal::from_i128((y[n] - top_int).abs()).unwrap());
         } else {
@@ -52,7 +60,7 @@ fn test_error_function(
         }
     }
 
-    error_vec
+    Ok(error_vec)
 }
 
 fn make_train_df() -> DataFrame {
@@ -70,14 +78,14 @@ fn simplification_function_test() {
 
     // push program declaration
     let push_program: Vec<Gene> = vec![
-        Gene::StateFunc(int_inc),   // Should get simplified out
-        Gene::StateFunc(float_tan), // along with all these float instructions
-        Gene::StateFunc(float_sub),
-        Gene::StateFunc(int_add), // stays
-        Gene::StateFunc(float_tan),
-        Gene::StateFunc(float_sub),
-        Gene::StateFunc(float_rem),
-        Gene::StateFunc(float_inc),
+        Gene::StateFunc(instruction!(int_inc)), // Should get simplified out
+        Gene::StateFunc(instruction!(float_tan)), // along with all these float instructions
+        Gene::StateFunc(instruction!(float_sub)),
+        Gene::StateFunc(instruction!(int_add)), // stays
+        Gene::StateFunc(instruction!(float_tan)),
+        Gene::StateFunc(instruction!(float_sub)),
+        Gene::StateFunc(instruction!(float_rem)),
+        Gene::StateFunc(instruction!(float_inc)),
         Gene::Place(0), // stays
         Gene::Place(1), // stays
     ];
@@ -92,9 +100,15 @@ fn simplification_function_test() {
     // test_error_function(&args, &train_df, push_program);
 
     // test the auto simplification here
-    let simplified_genome = auto_simplify_plushy(push_program, args.error_function.unwrap(), &args);
+    let mut rng = StdRng::seed_from_u64(42);
+    let simplified_genome =
+        auto_simplify_plushy(push_program, args.error_function.unwrap(), &args, &mut rng);
     assert_eq!(
-        vec![Gene::StateFunc(int_add), Gene::Place(0), Gene::Place(1)],
+        vec![
+            Gene::StateFunc(instruction!(int_add)),
+            Gene::Place(0),
+            Gene::Place(1)
+        ],
         simplified_genome
     )
 }
@@ -115,8 +129,8 @@ fn tournament_selection_test() {
     let mut individuals: Vec<Individual> = Vec::with_capacity(5);
 
     let plushy = vec![
-        Gene::StateFunc(int_add),
-        Gene::StateFunc(int_add),
+        Gene::StateFunc(instruction!(int_add)),
+        Gene::StateFunc(instruction!(int_add)),
         Gene::GeneInt(9999),
         Gene::Place(0),
         Gene::Place(1),
@@ -125,8 +139,8 @@ fn tournament_selection_test() {
     individuals.push(individual);
 
     let plushy = vec![
-        Gene::StateFunc(int_add),
-        Gene::StateFunc(int_add),
+        Gene::StateFunc(instruction!(int_add)),
+        Gene::StateFunc(instruction!(int_add)),
         Gene::GeneInt(9000),
         Gene::Place(0),
         Gene::Place(1),
@@ -135,8 +149,8 @@ fn tournament_selection_test() {
     individuals.push(individual);
 
     let plushy = vec![
-        Gene::StateFunc(int_add),
-        Gene::StateFunc(int_add),
+        Gene::StateFunc(instruction!(int_add)),
+        Gene::StateFunc(instruction!(int_add)),
         Gene::GeneInt(420),
         Gene::Place(0),
         Gene::Place(1),
@@ -145,8 +159,8 @@ fn tournament_selection_test() {
     individuals.push(individual);
 
     let plushy = vec![
-        Gene::StateFunc(int_add),
-        Gene::StateFunc(int_add),
+        Gene::StateFunc(instruction!(int_add)),
+        Gene::StateFunc(instruction!(int_add)),
         Gene::GeneInt(69),
         Gene::Place(0),
         Gene::Place(1),
@@ -155,8 +169,8 @@ fn tournament_selection_test() {
     individuals.push(individual);
 
     let plushy = vec![
-        Gene::StateFunc(int_add),
-        Gene::StateFunc(int_add),
+        Gene::StateFunc(instruction!(int_add)),
+        Gene::StateFunc(instruction!(int_add)),
         Gene::GeneInt(42),
         Gene::Place(0),
         Gene::Place(1),
@@ -165,12 +179,12 @@ fn tournament_selection_test() {
     individuals.push(individual);
 
     // For minimization problem.
-    let winning_ind = select_parent(individuals.clone(), &args, &mut rng);
+    let winning_ind = select_parent(&individuals, &args, &mut rng);
     assert_eq!(Some(dec!(126.0)), winning_ind.total_fitness);
 
     // For maximization problem.
     args.search_direction = SearchDirection::Max;
-    let winning_ind = select_parent(individuals.clone(), &args, &mut rng);
+    let winning_ind = select_parent(&individuals, &args, &mut rng);
     assert_eq!(Some(dec!(29997.0)), winning_ind.total_fitness);
 }
 
@@ -215,19 +229,129 @@ fn lexicase_selection_test() {
     let individual = Individual::with_error(plushy, test_error_function, &args, &train_df);
     individuals.push(individual);
 
-    let winning_ind = select_parent(individuals.clone(), &args, &mut rng);
+    let winning_ind = select_parent(&individuals, &args, &mut rng);
     assert_eq!(
         Individual::with_error(vec![Gene::Place(2)], test_error_function, &args, &train_df),
-        winning_ind
+        *winning_ind
     );
 
     let plushy = vec![Gene::Place(2)];
     let individual = Individual::with_error(plushy, test_error_function, &args, &train_df);
     individuals.push(individual);
 
-    let winning_ind = select_parent(individuals.clone(), &args, &mut rng);
+    let winning_ind = select_parent(&individuals, &args, &mut rng);
     assert_eq!(
         Individual::with_error(vec![Gene::Place(1)], test_error_function, &args, &train_df),
-        winning_ind
+        *winning_ind
     );
 }
+
+#[test]
+fn error_penalty_test() {
+    let train_df: DataFrame = make_train_df();
+
+    let mut args = PushArgs::new();
+    args.training_data = Some(train_df.clone());
+    args.error_penalty = dec!(5000.0);
+
+    // There are only two inputs, so the program can't run.
+    let plushy = vec![Gene::Place(0), Gene::Place(2)];
+    let individual = Individual::with_error(plushy, test_error_function, &args, &train_df);
+    assert_eq!(Some(vec![dec!(5000.0); 3]), individual.fitness_cases);
+    assert_eq!(Some(dec!(15000.0)), individual.total_fitness);
+}
+
+#[test]
+fn missing_input_test() {
+    let train_df: DataFrame = make_train_df();
+
+    let mut args = PushArgs::new();
+    args.training_data = Some(train_df.clone());
+    args.error_penalty = dec!(5000.0);
+
+    // The interpreter error comes back from the error function, not a panic.
+    let program = vec![Gene::Place(0), Gene::Place(2)];
+    assert_eq!(
+        Err(InterpreterError::MissingInput(2)),
+        test_error_function(&args, &train_df, program.clone())
+    );
+    // evaluate logs it and penalizes every case.
+    assert_eq!(
+        vec![dec!(5000.0); 3],
+        evaluate(test_error_function, &args, &train_df, program)
+    );
+}
+
+#[test]
+fn max_error_penalty_test() {
+    let train_df: DataFrame = make_train_df();
+
+    let mut args = PushArgs::new();
+    args.training_data = Some(train_df.clone());
+    args.error_penalty = dec!(5000.0);
+    args.search_direction = SearchDirection::Max;
+
+    // The penalty is the worst error when maximizing too
+    let plushy = vec![Gene::Place(0), Gene::Place(2)];
+    let broken = Individual::with_error(plushy, test_error_function, &args, &train_df);
+    assert_eq!(Some(vec![dec!(-5000.0); 3]), broken.fitness_cases);
+
+    let plushy = vec![Gene::Place(0)];
+    let runnable = Individual::with_error(plushy, test_error_function, &args, &train_df);
+    let mut rng = StdRng::seed_from_u64(42);
+    args.parent_selection = Selection::Tournament;
+    let individuals = vec![broken, runnable.clone()];
+    let winning_ind = select_parent(&individuals, &args, &mut rng);
+    assert_eq!(&runnable, winning_ind);
+}
+
+/// Error function that panics on programs without an int literal.
+fn panicking_error_function(
+    push_args: &PushArgs,
+    data: &DataFrame,
+    push_program: Vec<Gene>,
+) -> Result<Vec<Decimal>, InterpreterError> {
+    if !push_program
+        .iter()
+        .any(|gene| matches!(gene, Gene::GeneInt(_)))
+    {
+        panic!("No int literal in the program");
+    }
+    test_error_function(push_args, data, push_program)
+}
+
+#[test]
+fn evaluation_panic_test() {
+    let train_df: DataFrame = make_train_df();
+
+    let mut args = PushArgs::new();
+    args.training_data = Some(train_df.clone());
+    args.error_penalty = dec!(5000.0);
+
+    let plushy = vec![Gene::Place(0), Gene::Place(1)];
+    let individual = Individual::with_error(plushy, panicking_error_function, &args, &train_df);
+    assert_eq!(Some(vec![dec!(5000.0); 3]), individual.fitness_cases);
+
+    // Later evaluations still run normally
+    let plushy = vec![Gene::GeneInt(0), Gene::Place(0)];
+    let individual = Individual::with_error(plushy, panicking_error_function, &args, &train_df);
+    assert_eq!(
+        Some(vec![dec!(11), dec!(13), dec!(15)]),
+        individual.fitness_cases
+    );
+}
+
+#[test]
+fn max_evaluation_panic_test() {
+    let train_df: DataFrame = make_train_df();
+
+    let mut args = PushArgs::new();
+    args.training_data = Some(train_df.clone());
+    args.error_penalty = dec!(5000.0);
+    args.search_direction = SearchDirection::Max;
+
+    let plushy = vec![Gene::Place(0), Gene::Place(1)];
+    let individual = Individual::with_error(plushy, panicking_error_function, &args, &train_df);
+    assert_eq!(Some(vec![dec!(-5000.0); 3]), individual.fitness_cases);
+    assert_eq!(Some(dec!(-15000.0)), individual.total_fitness);
+}
@@ -0,0 +1,460 @@
+use polars::prelude::*;
+use rush::gp::args::{DownsampleType, PushArgs};
+use rush::gp::checkpoint::{Checkpoint, CheckpointError};
+use rush::gp::individual::Individual;
+use rush::gp::reporter::{JsonLinesReporter, QuietReporter, Reporter};
+use rush::gp::result::{GenerationSummary, RunResult};
+use rush::gp::utils::polars_to_gene;
+use rush::gp::{gp_loop, gp_loop_with_reporter, resume_gp_loop};
+use rush::instruction;
+use rush::instructions::common::*;
+use rush::instructions::numeric::*;
+use rush::push::interpreter::{InterpreterError, interpret_program};
+use rush::push::state::{EMPTY_STATE, Gene};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::{Decimal, dec};
+
+fn error_function(
+    push_args: &PushArgs,
+    data: &DataFrame,
+    push_program: Vec<Gene>,
+) -> Result<Vec<Decimal>, InterpreterError> {
+    let mut error_vec: Vec<Decimal> = vec![];
+
+    let y = data
+        .column("y")
+        .unwrap()
+        .i64()
+        .unwrap()
+        .into_iter()
+        .map(|opt| opt.map(|v| v as i128))
+        .collect::<Option<Vec<_>>>()
+        .unwrap();
+    let x = data.drop("y").unwrap();
+
+    for n in 0..x.height() {
+        let mut state = EMPTY_STATE;
+        let row = x.get_row(n).unwrap();
+        state.exec.extend(push_program.clone().into_iter());
+        state.input.extend(row.0.iter().map(polars_to_gene));
+        interpret_program(
+            &mut state,
+            push_args.step_limit,
+            push_args.max_stack_size,
+            push_args.max_output_length,
+            push_args.max_points,
+            push_args.overflow,
+        )?;
+        if let Some(top_int) = state.int.pop() {
+            error_vec.push(Decimal::from_i128((y[n] - top_int).abs()).unwrap());
+        } else {
+            error_vec.push(dec!(999999.0))
+        }
+    }
+
+    Ok(error_vec)
+}
+
+fn train_data() -> DataFrame {
+    let range: Vec<i64> = (-5..=5).collect();
+    df!(
+        "x0" => range.clone(),
+        "y" => range.iter().map(|x| x * x + 3).collect::<Vec<i64>>(),
+    )
+    .unwrap()
+}
+
+fn small_args() -> PushArgs {
+    let mut push_args = PushArgs::new();
+    push_args.training_data = Some(train_data());
+    push_args.instructions = Some(vec![
+        Gene::Place(0),
+        Gene::StateFunc(instruction!(int_add)),
+        Gene::StateFunc(instruction!(int_sub)),
+        Gene::StateFunc(instruction!(int_mult)),
+        Gene::StateFunc(instruction!(int_dup)),
+        Gene::StateFunc(instruction!(exec_dup)),
+        Gene::Close,
+        Gene::GeneInt(1),
+    ]);
+    push_args.error_function = Some(error_function);
+    push_args.pop_size = 30;
+    push_args.max_generations = 5;
+    push_args.max_init_plushy_size = 20;
+    push_args.simplification_steps = 20;
+    push_args.simplification_verbose = false;
+    push_args
+}
+
+#[test]
+fn seeded_runs_are_identical_test() {
+    let mut push_args = small_args();
+    push_args.seed = Some(1234);
+
+    let first = gp_loop(push_args.clone()).unwrap();
+    let second = gp_loop(push_args.clone()).unwrap();
+    assert_eq!(first, second);
+
+    push_args.use_single_thread = true;
+    let single_threaded = gp_loop(push_args).unwrap();
+    assert_eq!(first, single_threaded);
+}
+
+#[test]
+fn downsampled_runs_test() {
+    for downsample_type in [DownsampleType::Random, DownsampleType::Informed] {
+        let mut push_args = small_args();
+        push_args.seed = Some(99);
+        push_args.downsample = true;
+        push_args.downsample_type = downsample_type;
+        push_args.downsample_rate = 0.3;
+        push_args.ds_parent_rate = 0.2;
+        push_args.ds_parent_gens = 2;
+        push_args.elitism = true;
+
+        let first = gp_loop(push_args.clone()).unwrap();
+        // The best individual is evaluated on every training case.
+        assert_eq!(
+            11,
+            first.best_individual.fitness_cases.clone().unwrap().len()
+        );
+        assert_eq!(first, gp_loop(push_args).unwrap());
+    }
+}
+
+#[test]
+fn best_so_far_test() {
+    for downsample in [false, true] {
+        let mut push_args = small_args();
+        push_args.seed = Some(3);
+        push_args.dont_end = true;
+        push_args.max_generations = 8;
+        push_args.elitism = false;
+        push_args.downsample = downsample;
+        push_args.downsample_rate = 0.3;
+        push_args.use_simplification = false;
+
+        let mut reporter = BestReporter::default();
+        let result = gp_loop_with_reporter(push_args.clone(), &mut reporter).unwrap();
+        // The best individual is never worse than any generation's best on
+        // all of the training cases.
+        let best = result.best_individual.total_fitness.unwrap();
+        assert_eq!(
+            best,
+            Individual::with_error(
+                result.best_individual.plushy.clone(),
+                error_function,
+                &push_args,
+                &train_data()
+            )
+            .total_fitness
+            .unwrap()
+        );
+        assert!(
+            reporter
+                .full_data_bests
+                .iter()
+                .all(|gen_best| best <= *gen_best)
+        );
+        assert!(reporter.full_data_bests.contains(&best));
+        if reporter.solved {
+            assert!(result.solution_found);
+        }
+    }
+}
+
+#[test]
+fn run_result_test() {
+    let mut push_args = small_args();
+    push_args.seed = Some(7);
+    push_args.dont_end = true;
+    push_args.testing_data = Some(train_data());
+
+    let result = gp_loop(push_args.clone()).unwrap();
+    assert_eq!(5, result.generations);
+    assert_eq!(6, result.generation_summaries.len());
+    // The best individual of the whole run is kept, not just the final
+    // generation's.
+    assert_eq!(
+        result.best_individual.total_fitness.unwrap(),
+        result
+            .generation_summaries
+            .iter()
+            .map(|summary| summary.best_total_fitness)
+            .min()
+            .unwrap()
+    );
+    assert_eq!(
+        result.solution_found,
+        result.best_individual.total_fitness.unwrap() <= dec!(0.0)
+    );
+    // The testing data is the training data here.
+    assert_eq!(result.best_individual.fitness_cases, result.test_errors);
+    assert_eq!(
+        result.simplified_individual.clone().unwrap().fitness_cases,
+        result.simplified_test_errors
+    );
+    // test_new_best is off.
+    assert!(
+        result
+            .generation_summaries
+            .iter()
+            .all(|summary| summary.best_test_total_fitness.is_none())
+    );
+
+    push_args.use_simplification = false;
+    assert_eq!(None, gp_loop(push_args).unwrap().simplified_individual);
+}
+
+#[test]
+fn test_new_best_test() {
+    let mut push_args = small_args();
+    push_args.seed = Some(7);
+    push_args.dont_end = true;
+    push_args.test_new_best = true;
+    push_args.testing_data = Some(train_data());
+
+    let result = gp_loop(push_args).unwrap();
+    // Any generation that improved on the best so far has its test error recorded.
+    let mut best = result.generation_summaries[0].best_total_fitness;
+    for summary in result.generation_summaries.iter().skip(1) {
+        if summary.best_total_fitness < best {
+            best = summary.best_total_fitness;
+            assert_eq!(Some(best), summary.best_test_total_fitness);
+        } else {
+            assert_eq!(None, summary.best_test_total_fitness);
+        }
+    }
+}
+
+/// Records the full data total fitness of every generation's best individual.
+#[derive(Default)]
+struct BestReporter {
+    full_data_bests: Vec<Decimal>,
+    solved: bool,
+}
+
+impl Reporter for BestReporter {
+    fn on_generation_end(&mut self, _summary: &GenerationSummary, pop: &[Individual]) {
+        let ind = Individual::with_error(
+            pop[0].plushy.clone(),
+            error_function,
+            &small_args(),
+            &train_data(),
+        );
+        self.full_data_bests.push(ind.total_fitness.unwrap());
+    }
+
+    fn on_solution_found(&mut self, _generation: usize, _ind: &Individual) {
+        self.solved = true;
+    }
+}
+
+/// Records which hooks were called and in what order.
+#[derive(Default)]
+struct RecordingReporter {
+    events: Vec<String>,
+}
+
+impl Reporter for RecordingReporter {
+    fn on_run_start(&mut self, _push_args: &PushArgs, seed: u64) {
+        self.events.push(format!("start {}", seed));
+    }
+
+    fn on_generation_end(&mut self, summary: &GenerationSummary, pop: &[Individual]) {
+        assert_eq!(summary.best_total_fitness, pop[0].total_fitness.unwrap());
+        self.events
+            .push(format!("generation {}", summary.generation));
+    }
+
+    fn on_solution_found(&mut self, generation: usize, _ind: &Individual) {
+        self.events.push(format!("solution {}", generation));
+    }
+
+    fn on_run_end(&mut self, result: &RunResult) {
+        self.events.push(format!("end {}", result.generations));
+    }
+}
+
+#[test]
+fn reporter_hooks_test() {
+    let mut push_args = small_args();
+    push_args.seed = Some(7);
+    push_args.dont_end = true;
+
+    let mut reporter = RecordingReporter::default();
+    let result = gp_loop_with_reporter(push_args.clone(), &mut reporter).unwrap();
+    assert_eq!(
+        result,
+        gp_loop_with_reporter(push_args, &mut QuietReporter).unwrap()
+    );
+
+    let events = reporter.events;
+    assert_eq!("start 7", events[0]);
+    assert_eq!("end 5", events[events.len() - 1]);
+    let generations: Vec<&String> = events
+        .iter()
+        .filter(|event| event.starts_with("generation"))
+        .collect();
+    assert_eq!(6, generations.len());
+    assert_eq!("generation 0", generations[0]);
+    assert!(
+        events
+            .iter()
+            .filter(|event| event.starts_with("solution"))
+            .count()
+            <= 1
+    );
+    assert_eq!(
+        result.solution_found,
+        events.iter().any(|event| event.starts_with("solution"))
+    );
+}
+
+#[test]
+fn json_lines_reporter_test() {
+    let mut push_args = small_args();
+    push_args.seed = Some(7);
+    push_args.dont_end = true;
+
+    let mut reporter = JsonLinesReporter::new(Vec::new());
+    let result = gp_loop_with_reporter(push_args, &mut reporter).unwrap();
+    assert!(reporter.error().is_none());
+    let output = String::from_utf8(reporter.into_inner()).unwrap();
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(result.generation_summaries.len(), lines.len());
+    for (line, summary) in lines.iter().zip(result.generation_summaries.iter()) {
+        let json: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert_eq!(summary.generation as u64, json["generation"]);
+        assert_eq!(
+            summary.best_total_fitness.to_f64().unwrap(),
+            json["best_total_fitness"]
+        );
+        assert_eq!(
+            summary.stats.lexicase_elite_counts.len(),
+            json["lexicase_elite_counts"].as_array().unwrap().len()
+        );
+    }
+}
+
+#[test]
+fn checkpoint_resume_test() {
+    for downsample in [false, true] {
+        let path = std::env::temp_dir().join(format!("rush_checkpoint_test_{}.bin", downsample));
+        let mut push_args = small_args();
+        push_args.seed = Some(11);
+        push_args.dont_end = true;
+        push_args.max_generations = 6;
+        push_args.downsample = downsample;
+        push_args.downsample_type = DownsampleType::Informed;
+        push_args.downsample_rate = 0.3;
+        push_args.ds_parent_rate = 0.2;
+        push_args.ds_parent_gens = 3;
+        push_args.checkpoint_gens = 4;
+        push_args.checkpoint_path = Some(path.to_str().unwrap().to_string());
+
+        let full = gp_loop_with_reporter(push_args.clone(), &mut QuietReporter).unwrap();
+        let checkpoint = Checkpoint::load(&path).unwrap();
+        assert_eq!(4, checkpoint.generation);
+        assert_eq!(downsample, !checkpoint.case_distances.is_empty());
+
+        // The seed comes from the checkpoint.
+        push_args.seed = None;
+        push_args.checkpoint_path = None;
+        let resumed = resume_gp_loop(push_args, checkpoint, &mut QuietReporter).unwrap();
+        assert_eq!(full.best_individual, resumed.best_individual);
+        assert_eq!(full.simplified_individual, resumed.simplified_individual);
+        assert_eq!(full.generations, resumed.generations);
+        // The checkpoint generation was reported by the original run.
+        assert_eq!(
+            full.generation_summaries[5..],
+            resumed.generation_summaries[..]
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn resume_reports_once_test() {
+    let path = std::env::temp_dir().join("rush_resume_reports_once_test.bin");
+    let mut push_args = small_args();
+    push_args.seed = Some(5);
+    push_args.dont_end = true;
+    push_args.max_generations = 3;
+    push_args.checkpoint_gens = 3;
+    push_args.checkpoint_path = Some(path.to_str().unwrap().to_string());
+    let mut full_reporter = RecordingReporter::default();
+    let full = gp_loop_with_reporter(push_args.clone(), &mut full_reporter).unwrap();
+    let mut checkpoint = Checkpoint::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    push_args.max_generations = 7;
+    push_args.checkpoint_path = None;
+    let mut reporter = RecordingReporter::default();
+    let resumed = resume_gp_loop(push_args.clone(), checkpoint.clone(), &mut reporter).unwrap();
+
+    // Together the two runs report every generation exactly once.
+    let generations: Vec<usize> = full
+        .generation_summaries
+        .iter()
+        .chain(resumed.generation_summaries.iter())
+        .map(|summary| summary.generation)
+        .collect();
+    assert_eq!((0..=7).collect::<Vec<usize>>(), generations);
+    let generation_events: Vec<&String> = full_reporter
+        .events
+        .iter()
+        .chain(reporter.events.iter())
+        .filter(|event| event.starts_with("generation"))
+        .collect();
+    assert_eq!(8, generation_events.len());
+    assert_eq!("generation 4", reporter.events[1]);
+
+    // A run that already found its solution isn't reported solved again.
+    checkpoint.best_individual.total_fitness = Some(dec!(0.0));
+    push_args.dont_end = false;
+    let mut reporter = RecordingReporter::default();
+    let resumed = resume_gp_loop(push_args, checkpoint, &mut reporter).unwrap();
+    assert!(resumed.solution_found);
+    assert!(resumed.generation_summaries.is_empty());
+    assert_eq!(vec!["start 5", "end 3"], reporter.events);
+}
+
+#[test]
+fn checkpoint_error_test() {
+    let path = std::env::temp_dir().join("rush_checkpoint_error_test.bin");
+    let mut push_args = small_args();
+    push_args.seed = Some(11);
+    push_args.dont_end = true;
+    push_args.max_generations = 2;
+    push_args.checkpoint_gens = 1;
+    push_args.checkpoint_path = Some(path.to_str().unwrap().to_string());
+    gp_loop_with_reporter(push_args.clone(), &mut QuietReporter).unwrap();
+    let checkpoint = Checkpoint::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // Resuming with other arguments fails instead of panicking.
+    let mut other_args = push_args.clone();
+    other_args.seed = Some(12);
+    assert!(matches!(
+        resume_gp_loop(other_args, checkpoint, &mut QuietReporter),
+        Err(CheckpointError::Seed {
+            checkpoint: 11,
+            args: 12
+        })
+    ));
+
+    // So does a checkpoint that can't be written.
+    push_args.checkpoint_path = Some(
+        path.join("missing_dir")
+            .join("checkpoint.bin")
+            .to_str()
+            .unwrap()
+            .to_string(),
+    );
+    assert!(matches!(
+        gp_loop_with_reporter(push_args, &mut QuietReporter),
+        Err(CheckpointError::Io(_))
+    ));
+}
@@ -0,0 +1,26 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rush::gp::genome::{make_random_plushy, plushy_to_push};
+use rush::push::parser::{parse_program, program_to_string};
+use rush::push::utils::most_genes;
+
+#[test]
+fn random_program_round_trip_test() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for _ in 0..50 {
+        let plushy = make_random_plushy(most_genes(), 50, &mut rng);
+        assert_eq!(plushy, parse_program(&program_to_string(&plushy)).unwrap());
+
+        let push_program = plushy_to_push(plushy);
+        assert_eq!(
+            push_program,
+            parse_program(&program_to_string(&push_program)).unwrap()
+        );
+    }
+}
+
+#[test]
+fn hand_written_program_test() {
+    let text = "( in0 in0 int_mult 3 int_add ( exec_dup ( 1 int_add ) ) )";
+    assert_eq!(text, program_to_string(&parse_program(text).unwrap()));
+}
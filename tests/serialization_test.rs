@@ -0,0 +1,44 @@
+use rush::gp::individual::Individual;
+use rush::instruction;
+use rush::instructions::code::{code_but_last, code_combine};
+use rush::push::serialization::{from_binary, from_json, to_binary, to_json};
+use rush::push::state::Gene;
+use rust_decimal::dec;
+
+#[test]
+fn individual_round_trip_test() {
+    let plushy = vec![
+        Gene::GeneInt(1),
+        Gene::StateFunc(instruction!(code_but_last)),
+        Gene::Block(vec![
+            Gene::GeneBoolean(true),
+            Gene::StateFunc(instruction!(code_combine)),
+        ]),
+        Gene::GeneFloat(dec!(9.0)),
+    ];
+    let ind = Individual {
+        push_program: Some(plushy.clone()),
+        plushy,
+        total_fitness: Some(dec!(3.5)),
+        fitness_cases: Some(vec![dec!(1.0), dec!(2.5)]),
+    };
+
+    let json = to_json(&ind).unwrap();
+    assert!(json.contains("{\"StateFunc\":\"code_but_last\"}"));
+    assert_eq!(ind, from_json::<Individual>(&json).unwrap());
+    assert_eq!(
+        ind,
+        from_binary::<Individual>(&to_binary(&ind).unwrap()).unwrap()
+    );
+
+    let unevaluated = Individual {
+        plushy: vec![],
+        push_program: None,
+        total_fitness: None,
+        fitness_cases: None,
+    };
+    assert_eq!(
+        unevaluated,
+        from_json::<Individual>(&to_json(&unevaluated).unwrap()).unwrap()
+    );
+}